@@ -10,19 +10,29 @@ fn rand_vec(n: i32) -> Vec<i32> {
     (0..n).map(|_| rng.gen_range(0..1)).collect()
 }
 
-fn bench_naive(num_iter: u64) -> Vec<Vec<f64>> {
+/// creates one random input (matrix and vector) for every n in 1..=max_n,
+/// which is shared by all implementations
+fn rand_inputs(max_n: usize) -> Vec<(naive::Matrix<i32>, Vec<i32>)> {
+    (1..=max_n)
+        .map(|n| {
+            let m = naive::Matrix::new(n, n, rand_vec((n * n) as i32));
+            let v = rand_vec(n as i32);
+            (m, v)
+        })
+        .collect()
+}
+
+fn bench_naive(num_iter: u64, inputs: &[(naive::Matrix<i32>, Vec<i32>)]) -> Vec<Vec<f64>> {
     let mut times = Vec::new();
-    let max_n = 500;
 
     for _j in 0..num_iter {
         let mut times_per_iter = Vec::new();
 
-        for n in 1..=max_n {
-            let m = naive::Matrix::new(n, n, rand_vec((n * n) as i32));
-            let v = rand_vec(n as i32);
+        for (m, v) in inputs.iter() {
+            let v = v.clone();
 
             let now = Instant::now();
-            let _ = &m * v;
+            let _ = m * v;
             let elapsed = now.elapsed().as_nanos() as f64;
 
             times_per_iter.push(elapsed);
@@ -34,16 +44,15 @@ fn bench_naive(num_iter: u64) -> Vec<Vec<f64>> {
     times
 }
 
-fn bench_triples(num_iter: u64) -> Vec<Vec<f64>> {
+fn bench_triples(num_iter: u64, inputs: &[(naive::Matrix<i32>, Vec<i32>)]) -> Vec<Vec<f64>> {
     let mut times = Vec::new();
-    let max_n = 500;
 
     for _j in 0..num_iter {
         let mut times_per_iter = Vec::new();
 
-        for n in 1..=max_n {
-            let m = sparse_triples::Matrix::new(n, n, rand_vec((n * n) as i32));
-            let v = rand_vec(n as i32);
+        for (m, v) in inputs.iter() {
+            let m: sparse_triples::Matrix<i32> = m.clone().into();
+            let v = v.clone();
 
             let now = Instant::now();
             let _ = &m * v;
@@ -58,16 +67,15 @@ fn bench_triples(num_iter: u64) -> Vec<Vec<f64>> {
     times
 }
 
-fn bench_hash(num_iter: u64) -> Vec<Vec<f64>> {
+fn bench_hash(num_iter: u64, inputs: &[(naive::Matrix<i32>, Vec<i32>)]) -> Vec<Vec<f64>> {
     let mut times = Vec::new();
-    let max_n = 500;
 
     for _j in 0..num_iter {
         let mut times_per_iter = Vec::new();
 
-        for n in 1..=max_n {
-            let m = sparse_hash::Matrix::new(n, n, rand_vec((n * n) as i32));
-            let v = rand_vec(n as i32);
+        for (m, v) in inputs.iter() {
+            let m: sparse_hash::Matrix<i32> = m.clone().into();
+            let v = v.clone();
 
             let now = Instant::now();
             let _ = &m * v;
@@ -84,10 +92,11 @@ fn bench_hash(num_iter: u64) -> Vec<Vec<f64>> {
 
 fn main() {
     let num_iter = 100;
+    let inputs = rand_inputs(500);
 
-    let times_naive = bench_naive(num_iter);
-    let times_triples = bench_triples(num_iter);
-    let times_hash = bench_hash(num_iter);
+    let times_naive = bench_naive(num_iter, &inputs);
+    let times_triples = bench_triples(num_iter, &inputs);
+    let times_hash = bench_hash(num_iter, &inputs);
 
     let result = vec![
         ("naive".to_string(), style::RED, times_naive),
@@ -0,0 +1,144 @@
+use crate::algorithm::PathCount;
+use crate::graph::Graph;
+use crate::utils;
+
+/// # Colorful paths
+///
+/// Count the colorful `k`-paths of `g`, where the colors `1..=k` of the vertices are stored in
+/// `vert_data` (see `Graph::color_coding`). A path is colorful if all of its vertices have
+/// different colors. The paths are counted as directed paths, like in `c`.
+///
+/// The dynamic program of [Alon, Yuster and Zwick](https://doi.org/10.1145/210332.210337)
+/// counts the colorful paths that end in a vertex for every subset of colors, so it takes
+/// `O(2^k * (n + m))` time and `O(2^k * n)` memory.
+pub fn count_colorful(g: &Graph, k: usize) -> u64 {
+    if k == 0 {
+        return 0;
+    }
+    assert_eq!(
+        g.vert_data.len(),
+        g.num_vert(),
+        "every vertex needs a color in vert_data"
+    );
+    assert!(k < 32, "too many colors");
+
+    let color_bit = |v: usize| {
+        let c = g.vert_data[v];
+        assert!(1 <= c && c <= k, "colors must be in 1..=k");
+        1usize << (c - 1)
+    };
+
+    // paths[set * n + v]: colorful paths that use the colors in `set` and end in `v`
+    let n = g.num_vert();
+    let mut paths = vec![0u64; (1 << k) * n];
+    for v in 0..n {
+        paths[color_bit(v) * n + v] = 1;
+    }
+
+    // every extension adds a color, so the sets are processed in increasing order
+    for set in 1..(1usize << k) {
+        for v in 0..n {
+            let count = paths[set * n + v];
+            if count == 0 {
+                continue;
+            }
+            for u in g.neighbors_of(v) {
+                let bit = color_bit(u);
+                if set & bit == 0 {
+                    paths[(set | bit) * n + u] += count;
+                }
+            }
+        }
+    }
+
+    let all_colors = (1 << k) - 1;
+    paths[all_colors * n..].iter().sum()
+}
+
+/// # Color coding
+///
+/// Estimate the number of `k`-paths of `g` with the color coding method of
+/// [Alon, Yuster and Zwick](https://doi.org/10.1145/210332.210337). In every one of the
+/// `repetitions` the vertices are colored uniformly at random with `k` colors and the colorful
+/// paths are counted by `count_colorful`. A path is colorful with probability `k!/k^k`, so the
+/// mean of the scaled counts `colorful * k^k/k!` is an unbiased estimator.
+/// The counts follow the same convention as `c`, see `PathCount`.
+///
+/// Arguments:
+///
+/// * `g`: Graph, where the k-paths are counted
+/// * `k`: length of path
+/// * `repetitions`: number of random colorings, more repetitions reduce the variance
+pub fn color_coding(g: &Graph, k: usize, repetitions: usize) -> PathCount<f64> {
+    assert!(repetitions > 0, "at least one repetition is needed");
+
+    let scale = (k as f64).powi(k as i32) / utils::factorial(k) as f64;
+    let values: Vec<f64> = (0..repetitions)
+        .map(|_| count_colorful(&g.color_coding(k), k) as f64 * scale)
+        .collect();
+
+    PathCount::new(utils::mean(&values), g.directedness(), k)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::{color_coding, count_colorful, exact_count};
+    use crate::graph::{generators, Graph};
+
+    #[test]
+    fn colorful() {
+        let mut g = generators::path(3);
+        g.vert_data = vec![1, 2, 3];
+        assert_eq!(count_colorful(&g, 3), 2, "path in both directions");
+
+        g.vert_data = vec![1, 1, 2];
+        assert_eq!(count_colorful(&g, 3), 0, "no colorful 3-path");
+        assert_eq!(count_colorful(&g, 2), 2, "only the edge 1 - 2 is colorful");
+
+        g.vert_data = vec![1, 1, 1];
+        assert_eq!(count_colorful(&g, 1), 3, "every vertex is colorful");
+    }
+
+    #[test]
+    fn colorful_is_exact_with_distinct_colors() {
+        // with n = k colors all distinct, every k-path is colorful
+        let mut g = generators::petersen();
+        for k in [4, 6].iter() {
+            let mut sub = g.induced_subgraph(&(0..*k).collect::<Vec<_>>());
+            sub.vert_data = (1..=*k).collect();
+            assert_eq!(
+                count_colorful(&sub, *k),
+                exact_count(&sub, *k).directed,
+                "all paths are colorful"
+            );
+        }
+        g.vert_data = vec![1; 10];
+        assert_eq!(count_colorful(&g, 2), 0, "no colorful edge with one color");
+    }
+
+    #[test]
+    #[should_panic(expected = "every vertex needs a color in vert_data")]
+    fn uncolored() {
+        count_colorful(&generators::path(3), 2);
+    }
+
+    #[test]
+    fn estimator() {
+        let g = Graph::from_graph6("src/data/K10.g6");
+        let k = 3;
+        let eps = 0.3;
+        let expect = 720.;
+        let lower_bound = (1. - eps) * expect;
+        let upper_bound = (1. + eps) * expect;
+        let res = color_coding(&g, k, 200);
+        println!(
+            "lower: {}, res: {:?}, upper: {}",
+            lower_bound, res, upper_bound
+        );
+        assert!(
+            lower_bound <= res.directed && res.directed <= upper_bound,
+            "color coding is inside bounds"
+        );
+        assert_eq!(res.undirected, Some(res.directed / 2.));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::algorithm::exact::VisitedSet;
+use crate::algorithm::{estimate, PathCount};
+use crate::graph::{Directedness, Graph};
+use crate::matrix::parallel::Parallelism;
+use crate::utils;
+
+/// # Count cycles
+///
+/// Given an Graph `g` and an usize `k` as input, approximately count the number of `k`-cycles
+/// of `g`, like Algorithm C counts the `k`-paths. A `k`-cycle is a sequence of `k` distinct
+/// vertices `v_1, ..., v_k` with an edge from `v_i` to `v_(i+1)` and from `v_k` back to `v_1`,
+/// where all `k` rotations of the sequence are the same cycle.
+///
+/// Every sample is the closed walk sum of `Graph::compute_cycle_sum` for a random bernoulli
+/// coding, which counts every cycle once per start vertex, so it is divided by `k! * k`.
+/// The samples are averaged like in Algorithm C. Each sample costs `n * (k - 1)` matrix vector
+/// multiplications, see `compute_cycle_sum`, the in-neighbors of the vertices are collected
+/// only once.
+/// The counts follow the convention of `PathCount`: `directed` counts the cycles of an
+/// undirected Graph once per direction, so `undirected` divides out all `2k`
+/// rotations and reflections. An undirected Graph has no cycles with less than `3` vertices.
+///
+/// Arguments:
+///
+/// * `g`: Graph, where the k-cycles are counted
+/// * `k`: number of vertices of a cycle
+/// * `eps`: approximation accuracy
+pub fn count_cycles(g: &Graph, k: usize, eps: f64) -> PathCount<f64> {
+    if !has_cycle_length(g, k) || g.num_vert() < k {
+        return PathCount::new(0.0, g.directedness(), k);
+    }
+
+    // the in-neighbors are the same for every sample
+    let in_neighbors = g.try_in_neighbors().unwrap_or_else(|e| panic!("{}", e));
+    let denom = (utils::factorial(k) * k as u64) as f64;
+    let mean = estimate(g, k, eps, denom, |coding| {
+        g.try_cycle_sum_with(k, coding, &in_neighbors)
+            .unwrap_or_else(|e| panic!("{}", e))
+    });
+
+    PathCount::new(mean, g.directedness(), k)
+}
+
+/// # Exact count of cycles
+///
+/// Count the `k`-cycles of `g` exactly by a depth first search from every vertex `s` over the
+/// vertices larger than `s`, so every cycle is found once from its smallest vertex.
+/// The counts follow the same convention as `count_cycles`.
+pub fn exact_count_cycles(g: &Graph, k: usize) -> PathCount<u64> {
+    exact_count_cycles_parallel(g, k, &Parallelism::serial())
+}
+
+/// # Exact count of cycles in parallel
+///
+/// Count the `k`-cycles of `g` exactly like `exact_count_cycles`, where the smallest vertices
+/// of the cycles are split between the threads of `parallelism`
+pub fn exact_count_cycles_parallel(
+    g: &Graph,
+    k: usize,
+    parallelism: &Parallelism,
+) -> PathCount<u64> {
+    let count = if has_cycle_length(g, k) {
+        parallelism
+            .map_rows(g.num_vert(), |start| {
+                let mut visited = VisitedSet::new(g.num_vert());
+                cycles_from(g, start, start, k, &mut visited, false)
+            })
+            .into_iter()
+            .sum()
+    } else {
+        0
+    };
+
+    PathCount::new(count, g.directedness(), k)
+}
+
+/// # Has k-cycle
+///
+/// Decide exactly if `g` contains a `k`-cycle, see `count_cycles`. The depth first search of
+/// `exact_count_cycles` stops at the first cycle.
+pub fn has_k_cycle(g: &Graph, k: usize) -> bool {
+    has_cycle_length(g, k)
+        && (0..g.num_vert()).any(|start| {
+            let mut visited = VisitedSet::new(g.num_vert());
+            cycles_from(g, start, start, k, &mut visited, true) > 0
+        })
+}
+
+/// ## has_cycle_length
+///
+/// whether `g` can contain cycles with `k` vertices at all
+fn has_cycle_length(g: &Graph, k: usize) -> bool {
+    match g.directedness() {
+        Directedness::Directed => k >= 2,
+        Directedness::Undirected => k >= 3,
+    }
+}
+
+/// ## cycles_from
+///
+/// the number of paths with `k` vertices from `v` over vertices larger than `start` and not
+/// `visited`, that have an edge back to `start`. If `first` is set, the search stops after the
+/// first cycle.
+fn cycles_from(
+    g: &Graph,
+    start: usize,
+    v: usize,
+    k: usize,
+    visited: &mut VisitedSet,
+    first: bool,
+) -> u64 {
+    if k == 1 {
+        return g.has_edge(v, start) as u64;
+    }
+
+    visited.insert(v);
+    let mut count = 0;
+    for u in g.neighbors_of(v) {
+        if u > start && !visited.contains(u) {
+            count += cycles_from(g, start, u, k - 1, visited, first);
+            if first && count > 0 {
+                break;
+            }
+        }
+    }
+    visited.remove(v);
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::{
+        count_cycles, exact_count_cycles, exact_count_cycles_parallel, has_k_cycle,
+    };
+    use crate::graph::{generators, Graph};
+    use crate::matrix::parallel::Parallelism;
+
+    #[test]
+    fn complete_graph() {
+        // n! / ((n - k)! * 2k) undirected cycles
+        let g = generators::complete(5);
+        let expect = [(3, 10), (4, 15), (5, 12)];
+        for (k, cycles) in expect.iter() {
+            let count = exact_count_cycles(&g, *k);
+            assert_eq!(count.undirected, Some(*cycles), "undirected cycles of K5");
+            assert_eq!(count.directed, 2 * cycles, "both directions of every cycle");
+            assert!(has_k_cycle(&g, *k), "K5 has k-cycles");
+        }
+        assert_eq!(exact_count_cycles(&g, 2).directed, 0, "an edge is no cycle");
+        assert_eq!(
+            exact_count_cycles(&g, 6).directed,
+            0,
+            "no 6-cycle in 5 vertices"
+        );
+        assert!(!has_k_cycle(&g, 6), "no 6-cycle in 5 vertices");
+    }
+
+    #[test]
+    fn complete_graph_estimator() {
+        let g = generators::complete(6);
+        let eps = 0.5;
+        for (k, expect) in [(3, 20.), (4, 45.)].iter() {
+            let lower_bound = (1. - eps) * expect;
+            let upper_bound = (1. + eps) * expect;
+            let res = count_cycles(&g, *k, eps);
+            println!(
+                "lower: {}, res: {:?}, upper: {}",
+                lower_bound, res, upper_bound
+            );
+            let undirected = res.undirected.unwrap();
+            assert!(
+                lower_bound <= undirected && undirected <= upper_bound,
+                "number of cycles is inside bounds"
+            );
+            assert_eq!(res.directed, 2. * undirected);
+        }
+    }
+
+    #[test]
+    fn tutte_graph() {
+        let g = Graph::from_graph6("src/data/tutte_graph.g6");
+        assert!(!has_k_cycle(&g, 3), "tutte graph is triangle free");
+        assert_eq!(exact_count_cycles(&g, 3).directed, 0);
+        assert!(has_k_cycle(&g, 4), "tutte graph has girth 4");
+
+        let parallelism = Parallelism::new(4).with_min_rows_per_thread(1);
+        let expect = exact_count_cycles(&g, 4);
+        assert_eq!(
+            expect,
+            exact_count_cycles_parallel(&g, 4, &parallelism),
+            "result does not depend on the number of threads"
+        );
+
+        assert_eq!(
+            count_cycles(&g, 3, 0.5).directed,
+            0.,
+            "no closed walk survives"
+        );
+    }
+
+    #[test]
+    fn petersen() {
+        // the 12 pentagons of the petersen graph are its shortest cycles
+        let g = generators::petersen();
+        assert!(!has_k_cycle(&g, 4), "petersen graph has girth 5");
+        assert_eq!(exact_count_cycles(&g, 5).undirected, Some(12));
+    }
+
+    #[test]
+    fn directed() {
+        // 0 -> 1 -> 2 -> 0 and 1 -> 0
+        let g = Graph::from_edges(3, vec![(0, 1), (1, 2), (2, 0), (1, 0)]);
+        assert_eq!(exact_count_cycles(&g, 2).directed, 1, "0 -> 1 -> 0");
+        let count = exact_count_cycles(&g, 3);
+        assert_eq!(count.directed, 1, "only one direction");
+        assert_eq!(count.undirected, None, "directed graph");
+        assert!(!has_k_cycle(&g, 1), "no self loops");
+    }
+}
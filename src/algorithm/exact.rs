@@ -0,0 +1,157 @@
+use crate::algorithm::PathCount;
+use crate::graph::Graph;
+use crate::matrix::parallel::Parallelism;
+
+/// # Exact count
+///
+/// Count the `k`-paths of `g` exactly by enumerating all of them with a depth first search,
+/// which takes time proportional to the number of paths. The counts follow the same convention
+/// as `c`, see `PathCount`, so they can be used as ground truth for small and medium graphs.
+///
+/// Arguments:
+///
+/// * `g`: Graph, where the k-paths are counted
+/// * `k`: number of vertices of a path
+pub fn exact_count(g: &Graph, k: usize) -> PathCount<u64> {
+    exact_count_parallel(g, k, &Parallelism::serial())
+}
+
+/// # Exact count in parallel
+///
+/// Count the `k`-paths of `g` exactly like `exact_count`, where the start vertices of the paths
+/// are split between the threads of `parallelism`
+pub fn exact_count_parallel(g: &Graph, k: usize, parallelism: &Parallelism) -> PathCount<u64> {
+    let count = if k == 0 {
+        0
+    } else {
+        parallelism
+            .map_rows(g.num_vert(), |start| {
+                let mut visited = VisitedSet::new(g.num_vert());
+                paths_from(g, start, k, &mut visited)
+            })
+            .into_iter()
+            .sum()
+    };
+
+    PathCount::new(count, g.directedness(), k)
+}
+
+/// ## paths_from
+///
+/// the number of paths with `k` vertices that start at `v` and avoid all `visited` vertices
+fn paths_from(g: &Graph, v: usize, k: usize, visited: &mut VisitedSet) -> u64 {
+    if k == 1 {
+        return 1;
+    }
+
+    visited.insert(v);
+    let mut count = 0;
+    for u in g.neighbors_of(v) {
+        if !visited.contains(u) {
+            count += paths_from(g, u, k - 1, visited);
+        }
+    }
+    visited.remove(v);
+
+    count
+}
+
+/// # VisitedSet
+///
+/// the vertices of the current path, one bit per vertex
+pub(crate) struct VisitedSet {
+    words: Vec<u64>,
+}
+
+impl VisitedSet {
+    pub(crate) fn new(n: usize) -> Self {
+        VisitedSet {
+            words: vec![0; n.div_ceil(64)],
+        }
+    }
+
+    pub(crate) fn insert(&mut self, v: usize) {
+        self.words[v / 64] |= 1 << (v % 64);
+    }
+
+    pub(crate) fn remove(&mut self, v: usize) {
+        self.words[v / 64] &= !(1 << (v % 64));
+    }
+
+    pub(crate) fn contains(&self, v: usize) -> bool {
+        self.words[v / 64] & (1 << (v % 64)) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::{exact_count, exact_count_parallel};
+    use crate::graph::{generators, Graph};
+    use crate::matrix::parallel::Parallelism;
+
+    #[test]
+    fn closed_forms() {
+        for k in 1..=6 {
+            assert_eq!(
+                exact_count(&generators::complete(6), k).directed,
+                generators::complete_k_paths(6, k),
+                "complete graph"
+            );
+            assert_eq!(
+                exact_count(&generators::path(7), k).directed,
+                generators::path_k_paths(7, k),
+                "path graph"
+            );
+            assert_eq!(
+                exact_count(&generators::cycle(7), k).directed,
+                generators::cycle_k_paths(7, k),
+                "cycle graph"
+            );
+            assert_eq!(
+                exact_count(&generators::star(5), k).directed,
+                generators::star_k_paths(5, k),
+                "star graph"
+            );
+        }
+        for k in 1..=10 {
+            assert_eq!(
+                exact_count(&generators::petersen(), k).directed,
+                generators::petersen_k_paths(k),
+                "petersen graph"
+            );
+        }
+    }
+
+    #[test]
+    fn conventions() {
+        let g = Graph::from_graph6("src/data/path6.g6");
+        let count = exact_count(&g, 3);
+        assert_eq!(
+            count.directed, 8,
+            "every path is counted in both directions"
+        );
+        assert_eq!(count.undirected, Some(4), "number of undirected 3-paths");
+        assert_eq!(exact_count(&g, 7).directed, 0, "no 7-path in 6 vertices");
+        assert_eq!(exact_count(&g, 0).directed, 0, "no empty paths");
+
+        // binary tree with edges pointing down, like algorithm::tests::c_tree
+        let tree = Graph::from_edges(7, vec![(0, 1), (0, 4), (1, 2), (1, 3), (4, 5), (4, 6)]);
+        let count = exact_count(&tree, 2);
+        assert_eq!(count.directed, 6, "directed edges");
+        assert_eq!(count.undirected, None, "directed graph");
+        assert_eq!(exact_count(&tree, 3).directed, 4, "directed 3-paths");
+    }
+
+    #[test]
+    fn tutte_graph() {
+        let g = Graph::from_graph6("src/data/tutte_graph.g6");
+        let serial = exact_count(&g, 8);
+        let parallelism = Parallelism::new(4).with_min_rows_per_thread(1);
+        let parallel = exact_count_parallel(&g, 8, &parallelism);
+        assert_eq!(
+            serial, parallel,
+            "result does not depend on the number of threads"
+        );
+        assert_eq!(serial.directed, 2 * serial.undirected.unwrap());
+    }
+}
@@ -1,5 +1,61 @@
-use crate::{graph::Graph, utils};
+#[cfg(feature = "extensor_bitvec")]
+use crate::extensor::bitvec::ExTensor;
+#[cfg(feature = "extensor_dense_hashmap")]
+use crate::extensor::dense_hashmap::ExTensor;
+
+use crate::{
+    graph::{Directedness, Graph},
+    utils,
+};
 use num_traits::Zero;
+use std::ops::Div;
+
+mod color_coding;
+mod cycles;
+mod exact;
+mod witness;
+pub use color_coding::{color_coding, count_colorful};
+pub use cycles::{count_cycles, exact_count_cycles, exact_count_cycles_parallel, has_k_cycle};
+pub use exact::{exact_count, exact_count_parallel};
+pub use witness::{find_k_path, is_k_path};
+
+/// # PathCount
+///
+/// The number of `k`-paths of a Graph in both conventions:
+///
+/// * `directed`: the number of sequences of `k` distinct vertices `v_1, ..., v_k` with an edge
+///   from `v_i` to `v_(i+1)`. This is what `c` counts. In an undirected Graph every path with
+///   at least two vertices is counted once per direction.
+/// * `undirected`: the number of paths, where a path and its reverse are the same path.
+///   This is `directed / 2` for `k >= 2` and `directed` for `k = 1`.
+///   It is `None` for a directed Graph, since its paths can not be reversed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathCount<T> {
+    pub directed: T,
+    pub undirected: Option<T>,
+}
+
+impl<T> PathCount<T>
+where
+    T: Copy + Div<Output = T> + From<u8>,
+{
+    /// ## new
+    ///
+    /// Create the PathCount from the number of `directed` `k`-paths of a Graph with the given
+    /// `directedness`
+    pub fn new(directed: T, directedness: Directedness, k: usize) -> Self {
+        let undirected = match directedness {
+            Directedness::Directed => None,
+            Directedness::Undirected if k >= 2 => Some(directed / T::from(2)),
+            Directedness::Undirected => Some(directed),
+        };
+
+        PathCount {
+            directed,
+            undirected,
+        }
+    }
+}
 
 /// # Algorithm U
 ///
@@ -13,7 +69,7 @@ use num_traits::Zero;
 ///
 /// The algorithm is from [Brand, Dell and Husfeldt](https://arxiv.org/pdf/1804.09448.pdf)
 pub fn u(g: &Graph, k: usize) -> bool {
-    let vandermonde_mapping = utils::create_vandermonde(g.num_vert, k);
+    let vandermonde_mapping = utils::create_vandermonde(g.num_vert(), k);
     let res = g.compute_walk_sum(k, vandermonde_mapping);
     !res.is_zero()
 }
@@ -27,6 +83,8 @@ pub fn u(g: &Graph, k: usize) -> bool {
 /// ```not-a-test
 /// (1 - eps) * number of `k`-paths <= X <= (1 + eps) * number of `k`-paths
 /// ```
+/// The `k`-paths are counted as directed paths, so for an undirected Graph every path is
+/// counted in both directions, see `c_counts` and `PathCount`.
 ///
 /// Arguments:
 ///
@@ -36,21 +94,33 @@ pub fn u(g: &Graph, k: usize) -> bool {
 ///
 /// The algorithm is from [Brand, Dell and Husfeldt](https://arxiv.org/pdf/1804.09448.pdf)
 pub fn c(g: Graph, k: usize, eps: f64) -> f64 {
+    let denom = utils::factorial(k) as f64;
+    estimate(&g, k, eps, denom, |coding| g.compute_walk_sum(k, coding))
+}
+
+/// ## estimate
+///
+/// the sampling loop of Algorithm C: `sample` is evaluated for random bernoulli codings of the
+/// vertices of `g` and the mean of `|coefficient| / denom` is returned, as soon as the t-test
+/// bounds it by `(1 - eps)` (after at least `31` samples), or after `k^2 / eps^2` samples
+pub(crate) fn estimate<F>(g: &Graph, k: usize, eps: f64, denom: f64, mut sample: F) -> f64
+where
+    F: FnMut(Vec<ExTensor>) -> ExTensor,
+{
     let mut step = 1;
     let mut mean = f64::INFINITY;
     let mut values = Vec::new();
     let mut means = Vec::new();
 
     while step < ((k as f64).powf(2.0) / eps.powf(2.0)) as u32 {
-        let bernoulli_mapping = utils::create_bernoulli(g.num_vert, k);
-        let v_j = g.compute_walk_sum(k, bernoulli_mapping);
+        let bernoulli_mapping = utils::create_bernoulli(g.num_vert(), k);
+        let v_j = sample(bernoulli_mapping);
         let coeffs = if v_j.coeffs().is_empty() {
             0.0
         } else {
             v_j.coeffs()[0] as f64
         };
-        let denom = utils::factorial(k) as f64;
-        let x_j = (coeffs.abs() as f64) / denom;
+        let x_j = coeffs.abs() / denom;
         values.push(x_j);
 
         let n = step as f64;
@@ -69,6 +139,32 @@ pub fn c(g: Graph, k: usize, eps: f64) -> f64 {
     mean
 }
 
+/// # Algorithm C with both conventions
+///
+/// Run Algorithm C on `g` and report the number of directed and undirected `k`-paths,
+/// according to the `Directedness` of `g`, see `PathCount`.
+pub fn c_counts(g: Graph, k: usize, eps: f64) -> PathCount<f64> {
+    let directedness = g.directedness();
+    PathCount::new(c(g, k, eps), directedness, k)
+}
+
+/// # Algorithm C per component
+///
+/// Run Algorithm C on every connected component of `g` that has at least `k` vertices and
+/// sum the results. Every `k`-path lies inside one component, so this counts the same paths as
+/// `c` (in the same convention), but each `compute_walk_sum` only works on the vertices of one
+/// component.
+/// Each summand is only a `(1 ± eps)` approximation with probability `99%`, so for `m`
+/// components the sum is one with probability about `0.99^m`. Every component needs at least
+/// `31` samples, so with many small components this is slower than `c` on the whole Graph.
+pub fn c_per_component(g: Graph, k: usize, eps: f64) -> f64 {
+    g.connected_components()
+        .into_iter()
+        .filter(|component| component.len() >= k)
+        .map(|component| c(g.induced_subgraph(&component), k, eps))
+        .sum()
+}
+
 /// only used for benchmarking, returns the number of iterations
 pub fn c_count_iterations(g: Graph, k: usize, eps: f64) -> u32 {
     let mut step = 1u32;
@@ -76,7 +172,7 @@ pub fn c_count_iterations(g: Graph, k: usize, eps: f64) -> u32 {
     let mut means = Vec::new();
 
     while step < 100 * ((k as f64).powf(3.0) / eps.powf(2.0)) as u32 {
-        let bernoulli_mapping = utils::create_bernoulli(g.num_vert, k);
+        let bernoulli_mapping = utils::create_bernoulli(g.num_vert(), k);
         let v_j = g.compute_walk_sum(k, bernoulli_mapping);
         let coeffs = if v_j.coeffs().is_empty() {
             0.0
@@ -111,7 +207,7 @@ pub fn c_values_naive(g: Graph, k: usize, eps: f64) -> Vec<f64> {
     let mut means = Vec::new();
 
     while step < ((k as f64).powf(2.0) / eps.powf(2.0)) as u32 {
-        let bernoulli_mapping = utils::create_bernoulli(g.num_vert, k);
+        let bernoulli_mapping = utils::create_bernoulli(g.num_vert(), k);
         let v_j = g.compute_walk_sum(k, bernoulli_mapping);
         let coeffs = if v_j.coeffs().is_empty() {
             0.0
@@ -140,7 +236,7 @@ pub fn c_values_t_test(g: Graph, k: usize, eps: f64) -> Vec<f64> {
     let mut means = Vec::new();
 
     while step < ((k as f64).powf(2.0) / eps.powf(2.0)) as u32 {
-        let bernoulli_mapping = utils::create_bernoulli(g.num_vert, k);
+        let bernoulli_mapping = utils::create_bernoulli(g.num_vert(), k);
         let v_j = g.compute_walk_sum(k, bernoulli_mapping);
         let coeffs = if v_j.coeffs().is_empty() {
             0.0
@@ -177,7 +273,7 @@ pub fn c_values_std_dev(g: Graph, k: usize, eps: f64) -> Vec<f64> {
     let mut std_dev = f64::INFINITY;
 
     while std_dev > eps {
-        let bernoulli_mapping = utils::create_bernoulli(g.num_vert, k);
+        let bernoulli_mapping = utils::create_bernoulli(g.num_vert(), k);
         let v_j = g.compute_walk_sum(k, bernoulli_mapping);
         let coeffs = if v_j.coeffs().is_empty() {
             0.0
@@ -206,7 +302,7 @@ pub fn c_std_dev(g: Graph, k: usize, _eps: f64) -> Vec<f64> {
     let mut std_devs = Vec::new();
 
     while step < 4000 {
-        let bernoulli_mapping = utils::create_bernoulli(g.num_vert, k);
+        let bernoulli_mapping = utils::create_bernoulli(g.num_vert(), k);
         let v_j = g.compute_walk_sum(k, bernoulli_mapping);
         let coeffs = if v_j.coeffs().is_empty() {
             0.0
@@ -232,7 +328,8 @@ pub fn c_std_dev(g: Graph, k: usize, _eps: f64) -> Vec<f64> {
 
 #[cfg(test)]
 mod tests {
-    use crate::graph::Graph;
+    use crate::algorithm::PathCount;
+    use crate::graph::{Directedness, Graph, GraphBuilder};
     use crate::{algorithm, utils};
 
     #[test]
@@ -646,6 +743,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn c_per_component() {
+        // two triangles, a path with 2 vertices and an isolated vertex
+        let g = GraphBuilder::new(9)
+            .with_edges(vec![(0, 1), (1, 2), (2, 0), (3, 4), (5, 6), (6, 7), (7, 5)])
+            .with_directedness(Directedness::Undirected)
+            .build();
+        let k = 3;
+        let eps = 0.5;
+        let expect = 12.;
+        let lower_bound = (1. - eps) * expect;
+        let upper_bound = (1. + eps) * expect;
+        let res = algorithm::c_per_component(g, k, eps);
+        println!(
+            "lower: {}, res: {}, upper: {}",
+            lower_bound, res, upper_bound
+        );
+        assert!(
+            lower_bound <= res.abs() && res.abs() <= upper_bound,
+            "sum over the components is inside bounds"
+        );
+    }
+
+    #[test]
+    fn path_count() {
+        let count = PathCount::new(8u64, Directedness::Undirected, 3);
+        assert_eq!(count.directed, 8, "directed count is kept");
+        assert_eq!(
+            count.undirected,
+            Some(4),
+            "every path is counted in both directions"
+        );
+        let count = PathCount::new(6u64, Directedness::Undirected, 1);
+        assert_eq!(
+            count.undirected,
+            Some(6),
+            "a single vertex has one direction"
+        );
+        let count = PathCount::new(6u64, Directedness::Directed, 3);
+        assert_eq!(count.undirected, None, "directed paths can not be reversed");
+    }
+
+    #[test]
+    fn c_counts() {
+        let g = Graph::from_graph6("src/data/path6.g6");
+        assert_eq!(g.directedness(), Directedness::Undirected);
+        let k = 3;
+        let eps = 0.5;
+        let expect = 4.;
+        let lower_bound = (1. - eps) * expect;
+        let upper_bound = (1. + eps) * expect;
+        let res = algorithm::c_counts(g, k, eps);
+        println!(
+            "lower: {}, res: {:?}, upper: {}",
+            lower_bound, res, upper_bound
+        );
+        let undirected = res.undirected.expect("path graph is undirected");
+        assert_eq!(
+            res.directed,
+            2. * undirected,
+            "directed paths are counted twice"
+        );
+        assert!(
+            lower_bound <= undirected && undirected <= upper_bound,
+            "randomized counting algorithm c is inside bounds"
+        );
+    }
+
     #[test]
     fn complete_graph_10_3_path() {
         // Test algorithm c on complete graph with 10 vertices
@@ -0,0 +1,180 @@
+use crate::algorithm::exact::VisitedSet;
+use crate::graph::Graph;
+use crate::utils;
+use num_traits::Zero;
+
+/// number of random codings before `detect` decides that there is no `k`-path
+const DETECTION_ROUNDS: usize = 30;
+
+/// # Find k-path
+///
+/// Returns the vertices of a `k`-path of `g` in the order of the path, or `None` if no
+/// `k`-path was detected.
+///
+/// The path is found by self-reduction: every vertex is removed from the graph if a `k`-path
+/// is still detected afterwards. In the end every remaining vertex is on every remaining
+/// `k`-path, so exactly `k` vertices are left and their order is found by a depth first search.
+/// A `k`-path is detected if the walk sum of a random bernoulli coding (see `c`) does not vanish.
+/// A non vanishing walk sum proves that a `k`-path exists, but a `k`-path can be missed with a
+/// small probability, since every detection only tries `DETECTION_ROUNDS` codings.
+/// The returned path is always checked against the adjacency matrix.
+///
+/// Arguments:
+///
+/// * `g`: Graph, where to find the k-path
+/// * `k`: number of vertices of the path
+pub fn find_k_path(g: &Graph, k: usize) -> Option<Vec<usize>> {
+    if k == 0 || g.num_vert() < k {
+        return None;
+    }
+    if k == 1 {
+        return Some(vec![0]);
+    }
+
+    let mut vertices: Vec<usize> = (0..g.num_vert()).collect();
+    if !detect(&g.induced_subgraph(&vertices), k) {
+        return None;
+    }
+
+    let mut i = 0;
+    while i < vertices.len() && vertices.len() > k {
+        let mut without = vertices.clone();
+        without.remove(i);
+        if detect(&g.induced_subgraph(&without), k) {
+            vertices = without;
+        } else {
+            i += 1;
+        }
+    }
+
+    // vertex `j` of the remaining graph is vertex `vertices[j]` of `g`
+    let path: Vec<_> = first_path(&g.induced_subgraph(&vertices), k)?
+        .into_iter()
+        .map(|j| vertices[j])
+        .collect();
+    assert!(is_k_path(g, &path, k), "found sequence is not a k-path");
+
+    Some(path)
+}
+
+/// # Is k-path
+///
+/// Returns whether `path` consists of `k` distinct vertices of `g`, where consecutive vertices
+/// are connected by an edge
+pub fn is_k_path(g: &Graph, path: &[usize], k: usize) -> bool {
+    if path.len() != k || path.iter().any(|v| *v >= g.num_vert()) {
+        return false;
+    }
+
+    let mut visited = VisitedSet::new(g.num_vert());
+    for v in path {
+        if visited.contains(*v) {
+            return false;
+        }
+        visited.insert(*v);
+    }
+
+    path.windows(2).all(|e| g.has_edge(e[0], e[1]))
+}
+
+/// ## detect
+///
+/// decide if `g` contains a `k`-path, with one sided error
+fn detect(g: &Graph, k: usize) -> bool {
+    if g.num_vert() < k {
+        return false;
+    }
+
+    (0..DETECTION_ROUNDS).any(|_| {
+        let bernoulli_mapping = utils::create_bernoulli(g.num_vert(), k);
+        !g.compute_walk_sum(k, bernoulli_mapping).is_zero()
+    })
+}
+
+/// ## first_path
+///
+/// the first `k`-path of a depth first search over all start vertices
+fn first_path(g: &Graph, k: usize) -> Option<Vec<usize>> {
+    fn extend(g: &Graph, path: &mut Vec<usize>, k: usize, visited: &mut VisitedSet) -> bool {
+        if path.len() == k {
+            return true;
+        }
+
+        let v = *path.last().expect("path is not empty");
+        for u in g.neighbors_of(v) {
+            if !visited.contains(u) {
+                visited.insert(u);
+                path.push(u);
+                if extend(g, path, k, visited) {
+                    return true;
+                }
+                path.pop();
+                visited.remove(u);
+            }
+        }
+
+        false
+    }
+
+    let mut visited = VisitedSet::new(g.num_vert());
+    (0..g.num_vert()).find_map(|start| {
+        let mut path = vec![start];
+        visited.insert(start);
+        let found = extend(g, &mut path, k, &mut visited);
+        visited.remove(start);
+        if found {
+            Some(path)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::{find_k_path, is_k_path};
+    use crate::graph::{generators, Graph};
+
+    #[test]
+    fn path_graph() {
+        let g = Graph::from_graph6("src/data/path6.g6");
+        let path = find_k_path(&g, 6).expect("path graph has a 6-path");
+        assert!(
+            path == vec![0, 1, 2, 3, 4, 5] || path == vec![5, 4, 3, 2, 1, 0],
+            "the only 6-path"
+        );
+        assert_eq!(find_k_path(&g, 7), None, "no 7-path in 6 vertices");
+
+        let g = Graph::from_graph6("src/data/path3.g6");
+        assert_eq!(find_k_path(&g, 4), None, "no 4-path in a 3 path graph");
+    }
+
+    #[test]
+    fn petersen() {
+        let g = generators::petersen();
+        for k in 1..=5 {
+            let path = find_k_path(&g, k).expect("petersen graph has k-paths");
+            assert!(is_k_path(&g, &path, k), "result is a k-path");
+        }
+    }
+
+    #[test]
+    fn directed() {
+        // binary tree with edges pointing down, like algorithm::tests::c_tree
+        let g = Graph::from_edges(7, vec![(0, 1), (0, 4), (1, 2), (1, 3), (4, 5), (4, 6)]);
+        let path = find_k_path(&g, 3).expect("tree has 3-paths");
+        assert!(is_k_path(&g, &path, 3), "result follows the directed edges");
+        assert_eq!(path[0], 0, "every directed 3-path starts at the root");
+        assert_eq!(find_k_path(&g, 4), None, "tree has height 3");
+    }
+
+    #[test]
+    fn validate() {
+        let g = generators::cycle(4);
+        assert!(is_k_path(&g, &[3, 0, 1], 3), "path over the wrap around");
+        assert!(!is_k_path(&g, &[0, 2], 2), "no edge 0 - 2");
+        assert!(!is_k_path(&g, &[0, 1, 0], 3), "vertex is visited twice");
+        assert!(!is_k_path(&g, &[0, 1], 3), "wrong length");
+        assert!(!is_k_path(&g, &[0, 4], 2), "vertex out of bounds");
+    }
+}
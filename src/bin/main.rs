@@ -5,6 +5,9 @@ fn main() {
     let g = Graph::from_graph6("src/data/K20.g6");
     let k = 4;
     let eps = 0.1;
-    let res = algorithm::c(g, k, eps);
-    println!("res: {}", res)
+    let res = algorithm::c_counts(g, k, eps);
+    println!("directed: {}", res.directed);
+    if let Some(undirected) = res.undirected {
+        println!("undirected: {}", undirected);
+    }
 }
@@ -95,6 +95,22 @@ impl ExTensor {
     pub fn coeffs(&self) -> Vec<i64> {
         self.data.iter().map(|(_, coeff)| coeff.clone()).collect()
     }
+
+    /// ## terms
+    ///
+    /// Return the non zero `(basis, coefficient)` pairs of the ExTensor sorted by basis, so two
+    /// ExTensors are equal if and only if their terms are equal
+    #[cfg(test)]
+    pub(crate) fn terms(&self) -> Vec<(Vec<usize>, i64)> {
+        let mut res: Vec<_> = self
+            .data
+            .iter()
+            .filter(|(_, coeff)| **coeff != 0)
+            .map(|(base, coeff)| (base.indices(), *coeff))
+            .collect();
+        res.sort_unstable();
+        res
+    }
 }
 
 impl Zero for ExTensor {
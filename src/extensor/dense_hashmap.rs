@@ -72,6 +72,22 @@ impl ExTensor {
         }
         self.data.iter().map(|(_, coeff)| coeff.clone()).collect()
     }
+
+    /// ## terms
+    ///
+    /// Return the non zero `(basis, coefficient)` pairs of the ExTensor sorted by basis, so two
+    /// ExTensors are equal if and only if their terms are equal
+    #[cfg(test)]
+    pub(crate) fn terms(&self) -> Vec<(Vec<usize>, i64)> {
+        let mut res: Vec<_> = self
+            .data
+            .iter()
+            .filter(|(_, coeff)| **coeff != 0)
+            .map(|(base, coeff)| (base.iter().map(|b| *b as usize).collect(), *coeff))
+            .collect();
+        res.sort_unstable();
+        res
+    }
 }
 
 impl Zero for ExTensor {
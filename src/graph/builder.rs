@@ -0,0 +1,321 @@
+use crate::graph::{
+    AdjMat, BuildPolicy, Directedness, DroppedEdges, EdgePolicy, Graph, GraphError,
+};
+use crate::matrix::parallel::Parallelism;
+use std::collections::HashMap;
+
+/// # GraphBuilder
+///
+/// Collects the edges of a Graph with `num_vert` vertices and builds the sparse adjacency
+/// matrix directly from them, so memory proportional to the number of edges is needed
+/// instead of `num_vert * num_vert`.
+/// For an undirected Graph every edge only has to be added in one direction.
+/// Self loops and parallel edges are handled according to the `BuildPolicy`.
+///
+/// Example:
+///
+/// ```no code
+/// let g = GraphBuilder::new(3)
+///     .with_directedness(Directedness::Undirected)
+///     .with_edges(vec![(0, 1), (1, 2)])
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphBuilder {
+    num_vert: usize,
+    directedness: Directedness,
+    edges: Vec<(usize, usize)>,
+    policy: BuildPolicy,
+}
+
+impl GraphBuilder {
+    /// ## new
+    ///
+    /// Start building a directed Graph with `num_vert` vertices and no edges
+    pub fn new(num_vert: usize) -> Self {
+        GraphBuilder {
+            num_vert,
+            directedness: Directedness::Directed,
+            edges: Vec::new(),
+            policy: BuildPolicy::default(),
+        }
+    }
+
+    /// ## with_directedness
+    ///
+    /// Set whether the edges of the Graph are directed or undirected
+    pub fn with_directedness(mut self, directedness: Directedness) -> Self {
+        self.directedness = directedness;
+        self
+    }
+
+    /// ## with_policy
+    ///
+    /// Set how self loops and parallel edges are handled
+    pub fn with_policy(mut self, policy: BuildPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// ## add_edge
+    ///
+    /// Add an edge from vertex `from` to vertex `to` (indices start at `0`)
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        assert!(
+            from < self.num_vert && to < self.num_vert,
+            "vertex index out of bounds"
+        );
+        self.edges.push((from, to));
+    }
+
+    /// ## with_edges
+    ///
+    /// Add all `edges`, given as pairs `(from, to)`
+    pub fn with_edges<I>(mut self, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        for (from, to) in edges {
+            self.add_edge(from, to);
+        }
+        self
+    }
+
+    /// ## build
+    ///
+    /// Create the Graph. Panics if an edge is rejected by the `BuildPolicy`, see `try_build`.
+    pub fn build(self) -> Graph {
+        self.try_build().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_build
+    ///
+    /// Create the Graph, or return `GraphError::Rejected` for the first self loop or parallel
+    /// edge that is rejected by the `BuildPolicy`. The numbers of dropped edges are available
+    /// via `Graph::dropped_edges`.
+    pub fn try_build(self) -> Result<Graph, GraphError> {
+        let undirected = self.directedness == Directedness::Undirected;
+        let mut edges = self.edges;
+        let mut dropped_edges = DroppedEdges::default();
+
+        match self.policy.self_loops {
+            EdgePolicy::Reject => {
+                if let Some((v, _v)) = edges.iter().find(|(from, to)| from == to) {
+                    return Err(rejected("self loop is rejected", *v, *v));
+                }
+            }
+            EdgePolicy::Drop => {
+                let num_edges = edges.len();
+                edges.retain(|(from, to)| from != to);
+                dropped_edges.self_loops = num_edges - edges.len();
+            }
+            EdgePolicy::Keep => {}
+        }
+
+        // (u, v) and (v, u) are the same undirected edge
+        if undirected {
+            for (from, to) in edges.iter_mut() {
+                if from > to {
+                    std::mem::swap(from, to);
+                }
+            }
+        }
+        edges.sort_unstable();
+
+        // every edge with its multiplicity
+        let mut triplets: Vec<(usize, usize, u8)> = Vec::with_capacity(edges.len());
+        for (from, to) in edges {
+            match triplets.last_mut() {
+                Some((i, j, count)) if (*i, *j) == (from, to) => match self.policy.parallel_edges {
+                    EdgePolicy::Reject => {
+                        return Err(rejected("parallel edge is rejected", from, to));
+                    }
+                    EdgePolicy::Drop => dropped_edges.parallel_edges += 1,
+                    EdgePolicy::Keep => *count = count.saturating_add(1),
+                },
+                _ => triplets.push((from, to, 1)),
+            }
+        }
+
+        let adj_mat = AdjMat::from_triplets(self.num_vert, self.directedness, triplets);
+
+        Ok(Graph {
+            adj_mat: Box::new(adj_mat),
+            directedness: self.directedness,
+            parallelism: Parallelism::default(),
+            vert_data: Vec::new(),
+            vert_labels: Vec::new(),
+            label_indices: HashMap::new(),
+            edge_data: Vec::new(),
+            num_vert: self.num_vert,
+            dropped_edges,
+        })
+    }
+}
+
+fn rejected(message: &str, from: usize, to: usize) -> GraphError {
+    GraphError::Rejected {
+        message: String::from(message),
+        from,
+        to,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "matrix_naive")]
+    use crate::matrix::naive::Matrix;
+    #[cfg(feature = "matrix_sparse_hash")]
+    use crate::matrix::sparse_hash::Matrix;
+    #[cfg(feature = "matrix_sparse_triples")]
+    use crate::matrix::sparse_triples::Matrix;
+
+    use crate::graph::{
+        BuildPolicy, Directedness, DroppedEdges, EdgePolicy, Graph, GraphBuilder, GraphError,
+    };
+    use crate::utils;
+    use num_traits::Zero;
+
+    #[test]
+    fn directed() {
+        let g = GraphBuilder::new(3)
+            .with_edges(vec![(0, 1), (1, 2)])
+            .build();
+        assert_eq!(g.directedness(), Directedness::Directed);
+        assert_eq!(g.neighbors_of(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(g.neighbors_of(1).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(g.neighbors_of(2).count(), 0, "edges are directed");
+    }
+
+    #[test]
+    fn undirected() {
+        let mut builder = GraphBuilder::new(3).with_directedness(Directedness::Undirected);
+        builder.add_edge(0, 1);
+        builder.add_edge(2, 1);
+        builder.add_edge(1, 0);
+        let g = builder.build();
+
+        let expect = Graph::from(3, vec![0, 1, 0, 1, 0, 1, 0, 1, 0]);
+        for i in 0..3 {
+            assert_eq!(
+                g.neighbors_of(i).collect::<Vec<_>>(),
+                expect.neighbors_of(i).collect::<Vec<_>>(),
+                "edges are added in both directions"
+            );
+        }
+    }
+
+    #[test]
+    fn self_loops() {
+        let edges = vec![(0, 0), (0, 1), (1, 1)];
+        let g = GraphBuilder::new(2).with_edges(edges.clone()).build();
+        assert_eq!(
+            g.neighbors_of(1).collect::<Vec<_>>(),
+            vec![1],
+            "self loops are kept"
+        );
+
+        let policy = BuildPolicy::new().with_self_loops(EdgePolicy::Drop);
+        let g = GraphBuilder::new(2)
+            .with_policy(policy)
+            .with_edges(edges.clone())
+            .build();
+        assert_eq!(g.neighbors_of(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(g.neighbors_of(1).count(), 0, "self loops are dropped");
+        assert_eq!(
+            g.dropped_edges().self_loops,
+            2,
+            "number of dropped self loops"
+        );
+
+        let policy = BuildPolicy::new().with_self_loops(EdgePolicy::Reject);
+        let res = GraphBuilder::new(2)
+            .with_policy(policy)
+            .with_edges(edges)
+            .try_build();
+        assert!(
+            matches!(res, Err(GraphError::Rejected { from: 0, to: 0, .. })),
+            "first self loop is rejected"
+        );
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let edges = vec![(0, 1), (1, 2), (0, 1), (2, 1)];
+        let g = GraphBuilder::new(3).with_edges(edges.clone()).build();
+        assert_eq!(g.neighbors_of(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            g.dropped_edges(),
+            DroppedEdges {
+                self_loops: 0,
+                parallel_edges: 1
+            },
+            "directed edges in both directions are not parallel"
+        );
+
+        let g = GraphBuilder::new(3)
+            .with_directedness(Directedness::Undirected)
+            .with_edges(edges.clone())
+            .build();
+        assert_eq!(
+            g.dropped_edges().parallel_edges,
+            2,
+            "undirected edges in both directions are parallel"
+        );
+
+        let policy = BuildPolicy::new().with_parallel_edges(EdgePolicy::Keep);
+        let g = GraphBuilder::new(3)
+            .with_policy(policy)
+            .with_edges(edges.clone())
+            .build();
+        assert_eq!(
+            *g.adj_mat,
+            Matrix::new(3, 3, vec![0, 2, 0, 0, 0, 1, 0, 1, 0])
+        );
+        assert_eq!(
+            g.dropped_edges(),
+            DroppedEdges::default(),
+            "nothing is dropped"
+        );
+
+        let policy = BuildPolicy::new().with_parallel_edges(EdgePolicy::Reject);
+        let res = GraphBuilder::new(3)
+            .with_policy(policy)
+            .with_edges(edges)
+            .try_build();
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "parallel edge is rejected: edge (0, 1)"
+        );
+    }
+
+    #[test]
+    fn kept_parallel_edges_walk_sum() {
+        // 0 -> 1 twice, 1 -> 2 and 2 -> 3
+        let edges = vec![(0, 1), (1, 2), (0, 1), (2, 3)];
+        let k = 3;
+        for directedness in [Directedness::Directed, Directedness::Undirected].iter() {
+            let walk_sum = |policy: EdgePolicy| {
+                let g = GraphBuilder::new(4)
+                    .with_directedness(*directedness)
+                    .with_policy(BuildPolicy::new().with_parallel_edges(policy))
+                    .with_edges(edges.clone())
+                    .build();
+                g.compute_walk_sum(k, utils::create_vandermonde(4, k))
+            };
+            let dropped = walk_sum(EdgePolicy::Drop);
+            assert!(!dropped.is_zero(), "the graph has 3-paths");
+            assert_eq!(
+                walk_sum(EdgePolicy::Keep),
+                dropped,
+                "multiplicity of kept parallel edges is ignored"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex index out of bounds")]
+    fn out_of_bounds() {
+        GraphBuilder::new(2).with_edges(vec![(0, 2)]);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::graph::{AdjMat, Directedness, DroppedEdges, Graph};
+
+/// # Components
+///
+/// Split a Graph into smaller graphs. Every `k`-path lies inside one connected component, so
+/// the paths of the components can be counted separately.
+impl Graph {
+    /// ## induced_subgraph
+    ///
+    /// Create the Graph that consists of the given `vertices` and all edges between them.
+    /// Vertex `vertices[i]` becomes vertex `i` of the subgraph, its `vert_data`, `vert_labels`
+    /// and `edge_data` are kept.
+    pub fn induced_subgraph(&self, vertices: &[usize]) -> Self {
+        let mut index = vec![None; self.num_vert];
+        for (i, v) in vertices.iter().enumerate() {
+            assert!(*v < self.num_vert, "vertex index out of bounds");
+            assert!(index[*v].is_none(), "vertices must be distinct");
+            index[*v] = Some(i);
+        }
+
+        let undirected = self.directedness == Directedness::Undirected;
+        let edges = vertices
+            .iter()
+            .enumerate()
+            .flat_map(|(i, v)| self.neighbors_of(*v).map(move |u| (i, u)))
+            .filter_map(|(i, u)| Some((i, index[u]?)))
+            .filter(|(i, j)| !undirected || i <= j)
+            .collect();
+
+        let edge_data = self
+            .edge_data
+            .iter()
+            .filter_map(|e| {
+                let mut e = *e;
+                e.from = index[e.from]?;
+                e.to = index[e.to]?;
+                Some(e)
+            })
+            .collect();
+
+        let select = |data_len: usize| vertices.iter().filter(move |v| **v < data_len);
+        let vert_labels: Vec<_> = select(self.vert_labels.len())
+            .map(|v| self.vert_labels[*v].clone())
+            .collect();
+        Graph {
+            adj_mat: Box::new(AdjMat::from_edges(vertices.len(), self.directedness, edges)),
+            directedness: self.directedness,
+            parallelism: self.parallelism,
+            vert_data: select(self.vert_data.len())
+                .map(|v| self.vert_data[*v])
+                .collect(),
+            label_indices: Graph::index_labels(&vert_labels),
+            vert_labels,
+            edge_data,
+            num_vert: vertices.len(),
+            dropped_edges: DroppedEdges::default(),
+        }
+    }
+
+    /// ## connected_components
+    ///
+    /// Returns the vertices of every connected component, sorted by their smallest vertex.
+    /// The direction of the edges of a directed Graph is ignored (weakly connected components).
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        // union find with path halving
+        let mut parent: Vec<usize> = (0..self.num_vert).collect();
+        fn find(parent: &mut [usize], mut v: usize) -> usize {
+            while parent[v] != v {
+                parent[v] = parent[parent[v]];
+                v = parent[v];
+            }
+            v
+        }
+
+        for (i, j) in self.edges() {
+            let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+            // the smaller vertex is the root, so the roots are sorted like the components
+            parent[a.max(b)] = a.min(b);
+        }
+
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        let mut component_of = vec![0; self.num_vert];
+        for v in 0..self.num_vert {
+            let root = find(&mut parent, v);
+            if root == v {
+                component_of[v] = components.len();
+                components.push(vec![v]);
+            } else {
+                component_of[v] = component_of[root];
+                components[component_of[root]].push(v);
+            }
+        }
+
+        components
+    }
+
+    /// ## prune_for_k_paths
+    ///
+    /// Remove all vertices that can not be on a `k`-path, since their connected component has
+    /// less than `k` vertices. The remaining vertices keep their order, see `induced_subgraph`.
+    pub fn prune_for_k_paths(&self, k: usize) -> Self {
+        let mut vertices: Vec<_> = self
+            .connected_components()
+            .into_iter()
+            .filter(|c| c.len() >= k)
+            .flatten()
+            .collect();
+        vertices.sort_unstable();
+
+        self.induced_subgraph(&vertices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::neighbors;
+    use crate::graph::{generators, Directedness, Graph, GraphBuilder};
+
+    #[test]
+    fn induced_subgraph() {
+        let mut g = generators::cycle(5);
+        g.vert_data = vec![10, 11, 12, 13, 14];
+        let sub = g.induced_subgraph(&[4, 0, 1]);
+        assert_eq!(sub.num_vert(), 3, "number of vertices");
+        assert_eq!(sub.directedness(), Directedness::Undirected);
+        assert_eq!(
+            neighbors(&sub),
+            vec![vec![1], vec![0, 2], vec![1]],
+            "path 4 - 0 - 1"
+        );
+        assert_eq!(sub.vert_data, vec![14, 10, 11], "vertex data is kept");
+    }
+
+    #[test]
+    fn induced_subgraph_directed() {
+        let g = Graph::from_edges(4, vec![(0, 1), (1, 2), (2, 0), (2, 3)]);
+        let sub = g.induced_subgraph(&[2, 3, 0]);
+        assert_eq!(sub.directedness(), Directedness::Directed);
+        assert_eq!(
+            neighbors(&sub),
+            vec![vec![1, 2], vec![], vec![]],
+            "directed edges between the vertices"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "vertices must be distinct")]
+    fn induced_subgraph_duplicate() {
+        generators::path(3).induced_subgraph(&[0, 0]);
+    }
+
+    #[test]
+    fn connected_components() {
+        let g = GraphBuilder::new(7)
+            .with_edges(vec![(5, 1), (1, 3), (0, 4)])
+            .with_directedness(Directedness::Undirected)
+            .build();
+        assert_eq!(
+            g.connected_components(),
+            vec![vec![0, 4], vec![1, 3, 5], vec![2], vec![6]],
+            "components sorted by their smallest vertex"
+        );
+
+        // 2 -> 0 <- 1 is weakly connected
+        let g = Graph::from_edges(3, vec![(2, 0), (1, 0)]);
+        assert_eq!(
+            g.connected_components(),
+            vec![vec![0, 1, 2]],
+            "direction of the edges is ignored"
+        );
+    }
+
+    #[test]
+    fn prune_for_k_paths() {
+        // path 0 - 1 - 2, an edge 3 - 4 and an isolated vertex 5
+        let g = GraphBuilder::new(6)
+            .with_edges(vec![(0, 1), (1, 2), (3, 4)])
+            .with_directedness(Directedness::Undirected)
+            .build();
+        assert_eq!(
+            g.prune_for_k_paths(1).num_vert(),
+            6,
+            "every vertex is a 1-path"
+        );
+        assert_eq!(
+            neighbors(&g.prune_for_k_paths(2)),
+            vec![vec![1], vec![0, 2], vec![1], vec![4], vec![3]],
+            "isolated vertex is removed"
+        );
+        assert_eq!(
+            neighbors(&g.prune_for_k_paths(3)),
+            vec![vec![1], vec![0, 2], vec![1]],
+            "only the path is left"
+        );
+        assert_eq!(g.prune_for_k_paths(4).num_vert(), 0, "no 4-path");
+    }
+}
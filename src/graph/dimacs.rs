@@ -0,0 +1,155 @@
+use crate::graph::error::{self, bad_header, parse_token, parse_vertex};
+use crate::graph::{BuildPolicy, Directedness, Graph, GraphBuilder, GraphError};
+
+/// # DIMACS
+///
+/// Reader for the undirected graphs of the DIMACS challenges, e.g. the graph coloring instances.
+impl Graph {
+    /// ## from_dimacs
+    ///
+    /// Create an undirected Graph from the DIMACS file at `path_str`.
+    /// Lines prefixed by `c` are comments. The problem line `p edge n m` (or `p col n m`) has to
+    /// come before the `m` edge lines `e u v` (indices start at `1`).
+    /// Panics if the file is invalid, see `try_from_dimacs`.
+    pub fn from_dimacs(path_str: &str) -> Self {
+        Graph::try_from_dimacs(path_str).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_dimacs
+    ///
+    /// Create an undirected Graph from the DIMACS file at `path_str`, see `from_dimacs`
+    pub fn try_from_dimacs(path_str: &str) -> Result<Self, GraphError> {
+        Graph::try_from_dimacs_with_policy(path_str, BuildPolicy::default())
+    }
+
+    /// ## try_from_dimacs_with_policy
+    ///
+    /// Create an undirected Graph from the DIMACS file at `path_str`, where self loops and parallel
+    /// edges are handled according to `policy`, see `from_dimacs`
+    pub fn try_from_dimacs_with_policy(
+        path_str: &str,
+        policy: BuildPolicy,
+    ) -> Result<Self, GraphError> {
+        let file = std::fs::read_to_string(path_str)?;
+
+        // number of vertices and edges, and the line of the problem line
+        let mut problem = None;
+        let mut edges = Vec::new();
+
+        for (i, line) in file.lines().enumerate().map(|(i, line)| (i + 1, line)) {
+            let columns: Vec<_> = line.split_whitespace().collect();
+            match columns.first() {
+                None | Some(&"c") => {}
+                Some(&"p") => {
+                    if problem.is_some() {
+                        return Err(bad_header(i, "DIMACS file has two problem lines"));
+                    }
+                    if columns.len() != 4 || (columns[1] != "edge" && columns[1] != "col") {
+                        return Err(bad_header(i, "DIMACS problem line must be p edge n m"));
+                    }
+                    let n: usize = parse_token(line, columns[2], i)?;
+                    let m: usize = parse_token(line, columns[3], i)?;
+                    problem = Some((n, m, i));
+                }
+                Some(&"e") => {
+                    let (n, _m, _line) = problem.ok_or_else(|| {
+                        bad_header(i, "DIMACS problem line must come before the edges")
+                    })?;
+                    let columns = error::columns(line, i, 3)?;
+                    let u = parse_vertex(line, columns[1], i, n)?;
+                    let v = parse_vertex(line, columns[2], i, n)?;
+                    edges.push((u, v));
+                }
+                Some(_) => {
+                    let byte = line.len() - line.trim_start().len();
+                    return Err(GraphError::BadChar {
+                        line: i,
+                        byte,
+                        found: line[byte..].chars().next().unwrap_or(' '),
+                    });
+                }
+            }
+        }
+
+        let (n, m, problem_line) =
+            problem.ok_or_else(|| bad_header(1, "DIMACS problem line is missing"))?;
+        if edges.len() != m {
+            return Err(GraphError::SizeMismatch {
+                line: problem_line,
+                message: String::from("number of DIMACS edges does not match the problem line"),
+                expected: m,
+                found: edges.len(),
+            });
+        }
+
+        GraphBuilder::new(n)
+            .with_directedness(Directedness::Undirected)
+            .with_policy(policy)
+            .with_edges(edges)
+            .try_build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::{from_content, neighbors, TempFile};
+    use crate::graph::{Directedness, Graph, GraphError};
+
+    #[test]
+    fn tutte_graph() {
+        let g = Graph::from_dimacs("src/data/tutte_graph.col");
+        let expect = Graph::from_graph6("src/data/tutte_graph.g6");
+        assert_eq!(g.directedness(), Directedness::Undirected);
+        assert_eq!(neighbors(&g), neighbors(&expect), "tutte graph from DIMACS");
+    }
+
+    #[test]
+    #[should_panic(expected = "DIMACS problem line must come before the edges")]
+    fn edge_before_problem() {
+        from_content("dimacs_order", "e 1 2\np edge 2 1\n", Graph::from_dimacs);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of DIMACS edges does not match the problem line")]
+    fn wrong_edge_count() {
+        from_content(
+            "dimacs_count",
+            "p edge 3 3\ne 1 2\ne 2 3\n",
+            Graph::from_dimacs,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex index out of bounds")]
+    fn out_of_bounds() {
+        from_content("dimacs_bounds", "p edge 2 1\ne 1 3\n", Graph::from_dimacs);
+    }
+
+    #[test]
+    fn errors() {
+        let file = TempFile::new("dimacs_err");
+        let path_str = file.path_str();
+
+        file.write("c comment\np edge 3 1\n  x 1 2\n");
+        assert!(
+            matches!(
+                Graph::try_from_dimacs(path_str),
+                Err(GraphError::BadChar {
+                    line: 3,
+                    byte: 2,
+                    found: 'x'
+                })
+            ),
+            "unknown line type"
+        );
+
+        file.write("p edge 3 1\ne 1\n");
+        assert!(
+            matches!(
+                Graph::try_from_dimacs(path_str),
+                Err(GraphError::Truncated { line: 2, byte: 3 })
+            ),
+            "second vertex is missing"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::graph::{Directedness, Graph};
+use std::collections::HashSet;
+
+/// # DotOptions
+///
+/// Describes how a Graph is rendered by `Graph::to_dot_with`.
+/// By default all vertices are labeled and nothing is highlighted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DotOptions {
+    hide_labels: bool,
+    highlight: Vec<usize>,
+}
+
+impl DotOptions {
+    /// ## new
+    ///
+    /// Label all vertices, highlight nothing
+    pub fn new() -> Self {
+        DotOptions::default()
+    }
+
+    /// ## with_labels
+    ///
+    /// Set whether the vertices are labeled. The label is the name from `vert_labels` (or the
+    /// index) and the color from `vert_data`, if the Graph is colored.
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.hide_labels = !labels;
+        self
+    }
+
+    /// ## with_highlight
+    ///
+    /// Highlight the vertex sequence `path` and the edges between consecutive vertices of it
+    pub fn with_highlight(mut self, path: Vec<usize>) -> Self {
+        self.highlight = path;
+        self
+    }
+}
+
+/// # DOT
+///
+/// Export to the [DOT](https://graphviz.org/doc/info/lang.html) language of GraphViz, e.g.
+/// `dot -Tsvg graph.dot > graph.svg`
+impl Graph {
+    /// ## to_dot
+    ///
+    /// Render the Graph with labeled vertices, see `to_dot_with`
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(&DotOptions::new())
+    }
+
+    /// ## to_dot_with
+    ///
+    /// Render the Graph in the DOT language. An undirected Graph is written as `graph` where
+    /// every edge occurs once, a directed Graph as `digraph`.
+    /// If the vertices are colored (e.g. by color coding), they are filled with their color.
+    pub fn to_dot_with(&self, options: &DotOptions) -> String {
+        let undirected = self.directedness == Directedness::Undirected;
+        let (kind, edge_op) = if undirected {
+            ("graph", "--")
+        } else {
+            ("digraph", "->")
+        };
+
+        let path: HashSet<_> = options.highlight.iter().copied().collect();
+        let mut path_edges: HashSet<_> =
+            options.highlight.windows(2).map(|w| (w[0], w[1])).collect();
+        if undirected {
+            path_edges.extend(options.highlight.windows(2).map(|w| (w[1], w[0])));
+        }
+
+        let mut res = format!("{} {{\n", kind);
+        for i in 0..self.num_vert {
+            let mut attrs = Vec::new();
+            if !options.hide_labels {
+                let label = match self.vert_data.get(i) {
+                    Some(c) => format!("{} ({})", self.label_of(i), c),
+                    None => self.label_of(i),
+                };
+                // the backslash is escaped first, so the escaped quotes are kept
+                let label = label.replace('\\', "\\\\").replace('"', "\\\"");
+                attrs.push(format!("label=\"{}\"", label));
+            }
+            if let Some(c) = self.vert_data.get(i) {
+                // the brewer color scheme has 12 colors, colors start at 1
+                attrs.push(format!(
+                    "style=filled, colorscheme=set312, fillcolor={}",
+                    (c + 11) % 12 + 1
+                ));
+            }
+            if path.contains(&i) {
+                attrs.push(String::from("color=red, penwidth=2"));
+            }
+
+            if attrs.is_empty() {
+                res.push_str(&format!("  {};\n", i));
+            } else {
+                res.push_str(&format!("  {} [{}];\n", i, attrs.join(", ")));
+            }
+        }
+
+        for i in 0..self.num_vert {
+            for j in self.neighbors_of(i) {
+                if undirected && j < i {
+                    continue;
+                }
+                if path_edges.contains(&(i, j)) {
+                    res.push_str(&format!(
+                        "  {} {} {} [color=red, penwidth=2];\n",
+                        i, edge_op, j
+                    ));
+                } else {
+                    res.push_str(&format!("  {} {} {};\n", i, edge_op, j));
+                }
+            }
+        }
+        res.push_str("}\n");
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{DotOptions, Graph};
+
+    #[test]
+    fn undirected() {
+        let g = Graph::from_graph6("src/data/path3.g6");
+        let expect = "graph {\n  0 [label=\"0\"];\n  1 [label=\"1\"];\n  2 [label=\"2\"];\n  \
+                      0 -- 1;\n  1 -- 2;\n}\n";
+        assert_eq!(g.to_dot(), expect, "every undirected edge occurs once");
+    }
+
+    #[test]
+    fn highlight() {
+        let g = Graph::from_edges(3, vec![(0, 1), (1, 2), (2, 0)]);
+        let options = DotOptions::new()
+            .with_labels(false)
+            .with_highlight(vec![1, 2]);
+        let expect = "digraph {\n  0;\n  1 [color=red, penwidth=2];\n  \
+                      2 [color=red, penwidth=2];\n  0 -> 1;\n  \
+                      1 -> 2 [color=red, penwidth=2];\n  2 -> 0;\n}\n";
+        assert_eq!(
+            g.to_dot_with(&options),
+            expect,
+            "path 1 -> 2 is highlighted"
+        );
+    }
+
+    #[test]
+    fn colors() {
+        let mut g = Graph::from_edges(2, vec![(0, 1)]);
+        g.vert_data = vec![1, 13];
+        let dot = g.to_dot();
+        assert!(
+            dot.contains("0 [label=\"0 (1)\", style=filled, colorscheme=set312, fillcolor=1]"),
+            "colors are shown in the label and filled"
+        );
+        assert!(
+            dot.contains("1 [label=\"1 (13)\", style=filled, colorscheme=set312, fillcolor=1]"),
+            "colors wrap around"
+        );
+    }
+
+    #[test]
+    fn escape_labels() {
+        let mut g = Graph::from_edges(2, vec![(0, 1)]);
+        g.set_vert_labels(vec![String::from("a\\\"b"), String::from("c\\")]);
+        let dot = g.to_dot();
+        assert!(
+            dot.contains("0 [label=\"a\\\\\\\"b\"]"),
+            "backslash and quote are escaped"
+        );
+        assert!(
+            dot.contains("1 [label=\"c\\\\\"]"),
+            "trailing backslash does not escape the closing quote"
+        );
+    }
+}
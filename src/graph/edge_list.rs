@@ -0,0 +1,277 @@
+use crate::graph::{BuildPolicy, Directedness, Graph, GraphBuilder, GraphError};
+use std::collections::HashMap;
+
+/// # EdgeListOptions
+///
+/// Describes how the lines of an edge list file are split, see `Graph::from_edge_list`.
+/// By default the columns are separated by whitespace, lines starting with `#` are comments,
+/// the first two columns are the vertices of an edge and the edges are directed.
+///
+/// Example:
+///
+/// ```no code
+/// let options = EdgeListOptions::new()
+///     .with_delimiter(',')
+///     .with_columns(1, 2)
+///     .with_header(true);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeListOptions {
+    delimiter: Option<char>,
+    comment_prefix: String,
+    from_column: usize,
+    to_column: usize,
+    header: bool,
+    directedness: Directedness,
+    policy: BuildPolicy,
+}
+
+impl EdgeListOptions {
+    /// ## new
+    ///
+    /// Whitespace separated, directed edges in the first two columns, `#` comments
+    pub fn new() -> Self {
+        EdgeListOptions {
+            delimiter: None,
+            comment_prefix: String::from("#"),
+            from_column: 0,
+            to_column: 1,
+            header: false,
+            directedness: Directedness::Directed,
+            policy: BuildPolicy::default(),
+        }
+    }
+
+    /// ## with_delimiter
+    ///
+    /// Split the columns at `delimiter` (e.g. `,` for csv or `\t` for tsv) instead of whitespace.
+    /// Whitespace around the values is removed.
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// ## with_comment_prefix
+    ///
+    /// Skip all lines that start with `prefix`
+    pub fn with_comment_prefix(mut self, prefix: &str) -> Self {
+        self.comment_prefix = String::from(prefix);
+        self
+    }
+
+    /// ## with_columns
+    ///
+    /// Read the vertices of an edge from the columns `from` and `to` (starting at `0`)
+    pub fn with_columns(mut self, from: usize, to: usize) -> Self {
+        self.from_column = from;
+        self.to_column = to;
+        self
+    }
+
+    /// ## with_header
+    ///
+    /// Set whether the first line that is not a comment contains the column names
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// ## with_directedness
+    ///
+    /// Set whether the edges are directed or undirected
+    pub fn with_directedness(mut self, directedness: Directedness) -> Self {
+        self.directedness = directedness;
+        self
+    }
+
+    /// ## with_policy
+    ///
+    /// Set how self loops and parallel edges are handled, see `BuildPolicy`
+    pub fn with_policy(mut self, policy: BuildPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+impl Default for EdgeListOptions {
+    fn default() -> Self {
+        EdgeListOptions::new()
+    }
+}
+
+/// # edge list
+///
+/// Reader for edge lists with arbitrary vertex labels
+impl Graph {
+    /// ## from_edge_list
+    ///
+    /// Create a Graph from the edge list file at `path_str`, where every line contains one edge.
+    /// The vertices can be labeled by arbitrary strings. The labels are mapped to the indices
+    /// `0, 1, ...` in the order of their first occurrence and are kept in `vert_labels()`, so
+    /// `vert_labels()[i]` is the label of vertex `i`.
+    /// Panics if the file is invalid, see `try_from_edge_list`.
+    pub fn from_edge_list(path_str: &str, options: &EdgeListOptions) -> Self {
+        Graph::try_from_edge_list(path_str, options).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_edge_list
+    ///
+    /// Create a Graph from the edge list file at `path_str`, see `from_edge_list`
+    pub fn try_from_edge_list(
+        path_str: &str,
+        options: &EdgeListOptions,
+    ) -> Result<Self, GraphError> {
+        let file = std::fs::read_to_string(path_str)?;
+
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        let mut labels = Vec::new();
+        let mut index_of = |label: &str| {
+            *indices.entry(label.to_string()).or_insert_with(|| {
+                labels.push(label.to_string());
+                labels.len() - 1
+            })
+        };
+
+        let lines = file
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_i, line)| {
+                options.comment_prefix.is_empty() || !line.starts_with(&*options.comment_prefix)
+            })
+            .filter(|(_i, line)| !line.trim().is_empty())
+            .skip(options.header as usize);
+
+        let mut edges = Vec::new();
+        for (i, line) in lines {
+            let columns: Vec<_> = match options.delimiter {
+                Some(d) => line.split(d).map(|v| v.trim()).collect(),
+                None => line.split_whitespace().collect(),
+            };
+            let truncated = GraphError::Truncated {
+                line: i,
+                byte: line.trim_end().len(),
+            };
+            let (from, to) = match (
+                columns.get(options.from_column),
+                columns.get(options.to_column),
+            ) {
+                (Some(from), Some(to)) => (from, to),
+                _ => return Err(truncated),
+            };
+
+            edges.push((index_of(from), index_of(to)));
+        }
+
+        let mut g = GraphBuilder::new(labels.len())
+            .with_directedness(options.directedness)
+            .with_policy(options.policy)
+            .with_edges(edges)
+            .try_build()?;
+        g.set_vert_labels(labels);
+        Ok(g)
+    }
+
+    /// ## vert_labels
+    ///
+    /// Returns the labels of all vertices, where the `i`-th label belongs to vertex `i`.
+    /// It is empty if the Graph has no labels.
+    pub fn vert_labels(&self) -> &[String] {
+        &self.vert_labels
+    }
+
+    /// ## set_vert_labels
+    ///
+    /// Set the labels of all vertices and index them for `index_of_label`. If a label is given
+    /// multiple times, it refers to its first vertex.
+    pub fn set_vert_labels(&mut self, labels: Vec<String>) {
+        self.label_indices = Graph::index_labels(&labels);
+        self.vert_labels = labels;
+    }
+
+    /// ## label_of
+    ///
+    /// Returns the label of vertex `i`, or its index if the Graph has no labels.
+    /// Used to report results such as paths with the original names of the vertices.
+    pub fn label_of(&self, i: usize) -> String {
+        self.vert_labels
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| i.to_string())
+    }
+
+    /// ## index_of_label
+    ///
+    /// Returns the index of the vertex with the given `label`, if the Graph has labels.
+    /// The labels are indexed by a hash map, so this does not depend on the number of vertices.
+    pub fn index_of_label(&self, label: &str) -> Option<usize> {
+        self.label_indices.get(label).copied()
+    }
+
+    /// ## index_labels
+    ///
+    /// map every label to the first vertex with this label
+    pub(crate) fn index_labels(labels: &[String]) -> HashMap<String, usize> {
+        let mut indices = HashMap::with_capacity(labels.len());
+        for (i, label) in labels.iter().enumerate() {
+            indices.entry(label.clone()).or_insert(i);
+        }
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::from_content;
+    use crate::graph::{Directedness, EdgeListOptions, Graph};
+
+    #[test]
+    fn whitespace() {
+        let g = from_content(
+            "edge_list_ws",
+            "# a comment\nalice bob\nbob  carol\n\ncarol\talice\n",
+            |path| Graph::from_edge_list(path, &EdgeListOptions::new()),
+        );
+        assert_eq!(g.num_vert(), 3);
+        assert_eq!(
+            g.vert_labels(),
+            vec!["alice", "bob", "carol"],
+            "labels in order"
+        );
+        assert_eq!(g.neighbors_of(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(g.neighbors_of(2).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(g.index_of_label("carol"), Some(2));
+        assert_eq!(g.index_of_label("dave"), None);
+        assert_eq!(g.label_of(1), "bob");
+
+        let unlabeled = Graph::from_edges(2, vec![(0, 1)]);
+        assert_eq!(unlabeled.label_of(1), "1", "index without labels");
+    }
+
+    #[test]
+    fn csv_columns() {
+        let options = EdgeListOptions::new()
+            .with_delimiter(',')
+            .with_comment_prefix("//")
+            .with_columns(2, 1)
+            .with_header(true)
+            .with_directedness(Directedness::Undirected);
+        let g = from_content(
+            "edge_list_csv",
+            "// exported\nid,to,from\n1, x ,y\n2,z,y\n",
+            |path| Graph::from_edge_list(path, &options),
+        );
+        assert_eq!(g.directedness(), Directedness::Undirected);
+        assert_eq!(g.vert_labels(), vec!["y", "x", "z"], "header is skipped");
+        assert_eq!(g.neighbors_of(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(g.neighbors_of(2).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "data is truncated (line 2, byte 1)")]
+    fn too_few_columns() {
+        from_content("edge_list_columns", "a b\nc\n", |path| {
+            Graph::from_edge_list(path, &EdgeListOptions::new())
+        });
+    }
+}
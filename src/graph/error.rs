@@ -0,0 +1,229 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// # GraphError
+///
+/// Error of the `try_from_*` loaders and `GraphBuilder::try_build`. Lines are counted from `1`
+/// and `byte` is the offset of a character within its line (starting at `0`).
+#[derive(Debug)]
+pub enum GraphError {
+    /// the file could not be read
+    Io(std::io::Error),
+    /// the header or another line that describes the format is invalid
+    BadHeader { line: usize, message: String },
+    /// a character that is not allowed at this position
+    BadChar {
+        line: usize,
+        byte: usize,
+        found: char,
+    },
+    /// a token that only consists of number characters, but is out of range or of the wrong
+    /// kind, e.g. a negative or fractional vertex index
+    BadNumber {
+        line: usize,
+        byte: usize,
+        token: String,
+    },
+    /// the data ends before the graph is complete
+    Truncated { line: usize, byte: usize },
+    /// a number of vertices or edges, or a vertex index, does not match the header
+    SizeMismatch {
+        line: usize,
+        message: String,
+        expected: usize,
+        found: usize,
+    },
+    /// a self loop or parallel edge that is rejected by the `BuildPolicy`
+    Rejected {
+        message: String,
+        from: usize,
+        to: usize,
+    },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Io(e) => write!(f, "could not read file: {}", e),
+            GraphError::BadHeader { line, message } => write!(f, "{} (line {})", message, line),
+            GraphError::BadChar { line, byte, found } => write!(
+                f,
+                "unexpected character {:?} (line {}, byte {})",
+                found, line, byte
+            ),
+            GraphError::BadNumber { line, byte, token } => write!(
+                f,
+                "invalid number {:?} (line {}, byte {})",
+                token, line, byte
+            ),
+            GraphError::Truncated { line, byte } => {
+                write!(f, "data is truncated (line {}, byte {})", line, byte)
+            }
+            GraphError::SizeMismatch {
+                line,
+                message,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected {}, found {} (line {})",
+                message, expected, found, line
+            ),
+            GraphError::Rejected { message, from, to } => {
+                write!(f, "{}: edge ({}, {})", message, from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GraphError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GraphError {
+    fn from(e: std::io::Error) -> Self {
+        GraphError::Io(e)
+    }
+}
+
+/// ## bad_header
+///
+/// shorthand for a `BadHeader` error
+pub(crate) fn bad_header(line: usize, message: &str) -> GraphError {
+    GraphError::BadHeader {
+        line,
+        message: String::from(message),
+    }
+}
+
+/// ## parse_token
+///
+/// parse the whitespace free `token`, which is a slice of the line `line_str` with number
+/// `line`. If it is not a valid number, the error is a `BadChar` at the first character that
+/// can not be part of a number, or a `BadNumber` at the start of the token if there is no such
+/// character (e.g. on overflow).
+pub(crate) fn parse_token<T: FromStr>(
+    line_str: &str,
+    token: &str,
+    line: usize,
+) -> Result<T, GraphError> {
+    token.parse().map_err(|_| {
+        let start = token.as_ptr() as usize - line_str.as_ptr() as usize;
+        match token
+            .char_indices()
+            .find(|(_i, c)| !(c.is_ascii_digit() || "+-.eE".contains(*c)))
+        {
+            Some((offset, found)) => GraphError::BadChar {
+                line,
+                byte: start + offset,
+                found,
+            },
+            None => GraphError::BadNumber {
+                line,
+                byte: start,
+                token: String::from(token),
+            },
+        }
+    })
+}
+
+/// ## parse_vertex
+///
+/// parse the 1-based vertex index `token` (see `parse_token`) and return the 0-based index.
+/// The index must be in `1..=num_vert`.
+pub(crate) fn parse_vertex(
+    line_str: &str,
+    token: &str,
+    line: usize,
+    num_vert: usize,
+) -> Result<usize, GraphError> {
+    let v: usize = parse_token(line_str, token, line)?;
+    if v == 0 || v > num_vert {
+        return Err(GraphError::SizeMismatch {
+            line,
+            message: String::from("vertex index out of bounds"),
+            expected: num_vert,
+            found: v,
+        });
+    }
+
+    Ok(v - 1)
+}
+
+/// ## columns
+///
+/// split `line_str` at whitespace, there have to be at least `min_len` columns
+pub(crate) fn columns(
+    line_str: &str,
+    line: usize,
+    min_len: usize,
+) -> Result<Vec<&str>, GraphError> {
+    let columns: Vec<_> = line_str.split_whitespace().collect();
+    if columns.len() < min_len {
+        return Err(GraphError::Truncated {
+            line,
+            byte: line_str.trim_end().len(),
+        });
+    }
+
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::error::{parse_token, parse_vertex, GraphError};
+
+    #[test]
+    fn display() {
+        let e = GraphError::BadChar {
+            line: 3,
+            byte: 5,
+            found: 'x',
+        };
+        assert_eq!(e.to_string(), "unexpected character 'x' (line 3, byte 5)");
+        let e = GraphError::Truncated { line: 1, byte: 2 };
+        assert_eq!(e.to_string(), "data is truncated (line 1, byte 2)");
+    }
+
+    #[test]
+    fn parse() {
+        let line = "12 3x4";
+        let tokens: Vec<_> = line.split_whitespace().collect();
+        assert_eq!(parse_token::<usize>(line, tokens[0], 1).unwrap(), 12);
+        match parse_token::<usize>(line, tokens[1], 1) {
+            Err(GraphError::BadChar { line, byte, found }) => {
+                assert_eq!((line, byte, found), (1, 4, 'x'), "position of the bad char")
+            }
+            e => panic!("expected BadChar, got {:?}", e),
+        }
+        assert_eq!(parse_vertex(line, tokens[0], 1, 12).unwrap(), 11);
+        assert!(
+            matches!(
+                parse_vertex(line, tokens[0], 1, 11),
+                Err(GraphError::SizeMismatch { .. })
+            ),
+            "index is out of bounds"
+        );
+        let line = "1 99999999999999999999999 -2 1.5";
+        let tokens: Vec<_> = line.split_whitespace().collect();
+        for (token, byte) in tokens[1..].iter().zip([2, 26, 29].iter()) {
+            match parse_token::<usize>(line, token, 2) {
+                Err(GraphError::BadNumber { line, byte: b, .. }) => {
+                    assert_eq!((line, b), (2, *byte), "position of the bad number")
+                }
+                e => panic!("expected BadNumber, got {:?}", e),
+            }
+        }
+        assert_eq!(
+            parse_token::<usize>(line, tokens[3], 2)
+                .unwrap_err()
+                .to_string(),
+            "invalid number \"1.5\" (line 2, byte 29)"
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use crate::graph::{Directedness, Graph, GraphBuilder};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+/// ## undirected
+///
+/// build an undirected Graph with `n` vertices from its `edges`
+fn undirected<I>(n: usize, edges: I) -> Graph
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    GraphBuilder::new(n)
+        .with_directedness(Directedness::Undirected)
+        .with_edges(edges)
+        .build()
+}
+
+/// ## falling_factorial
+///
+/// `n * (n - 1) * ... * (n - k + 1)`, the number of sequences of `k` distinct elements out of `n`
+fn falling_factorial(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (n - k + 1..=n).map(|i| i as u64).product()
+}
+
+/// ## complete
+///
+/// the complete graph `K_n`
+pub fn complete(n: usize) -> Graph {
+    undirected(n, (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))))
+}
+
+/// ## complete_k_paths
+///
+/// number of `k`-paths in `K_n`: every sequence of `k` distinct vertices, `n! / (n - k)!`
+pub fn complete_k_paths(n: usize, k: usize) -> u64 {
+    if k == 0 {
+        return 0;
+    }
+    falling_factorial(n, k)
+}
+
+/// ## path
+///
+/// the path `0 - 1 - ... - (n - 1)` with `n` vertices
+pub fn path(n: usize) -> Graph {
+    undirected(n, (1..n).map(|i| (i - 1, i)))
+}
+
+/// ## path_k_paths
+///
+/// number of `k`-paths in the path with `n` vertices
+pub fn path_k_paths(n: usize, k: usize) -> u64 {
+    match k {
+        0 => 0,
+        1 => n as u64,
+        _ if k > n => 0,
+        _ => 2 * (n - k + 1) as u64,
+    }
+}
+
+/// ## cycle
+///
+/// the cycle `0 - 1 - ... - (n - 1) - 0` with `n >= 3` vertices
+pub fn cycle(n: usize) -> Graph {
+    assert!(n >= 3, "a cycle needs at least 3 vertices");
+    undirected(n, (0..n).map(|i| (i, (i + 1) % n)))
+}
+
+/// ## cycle_k_paths
+///
+/// number of `k`-paths in the cycle with `n` vertices: every start vertex in both directions
+pub fn cycle_k_paths(n: usize, k: usize) -> u64 {
+    match k {
+        0 => 0,
+        1 => n as u64,
+        _ if k > n => 0,
+        _ => 2 * n as u64,
+    }
+}
+
+/// ## star
+///
+/// the star with center `0` and the `n` leaves `1..=n`
+pub fn star(n: usize) -> Graph {
+    undirected(n + 1, (1..=n).map(|i| (0, i)))
+}
+
+/// ## star_k_paths
+///
+/// number of `k`-paths in the star with `n` leaves
+pub fn star_k_paths(n: usize, k: usize) -> u64 {
+    match k {
+        1 => n as u64 + 1,
+        2 => 2 * n as u64,
+        3 => falling_factorial(n, 2),
+        _ => 0,
+    }
+}
+
+/// ## grid
+///
+/// the `rows` x `cols` grid, vertex `(r, c)` has the index `r * cols + c`
+pub fn grid(rows: usize, cols: usize) -> Graph {
+    let horizontal =
+        (0..rows).flat_map(move |r| (1..cols).map(move |c| (r * cols + c - 1, r * cols + c)));
+    let vertical =
+        (1..rows).flat_map(move |r| (0..cols).map(move |c| ((r - 1) * cols + c, r * cols + c)));
+    undirected(rows * cols, horizontal.chain(vertical))
+}
+
+/// ## complete_bipartite
+///
+/// the complete bipartite graph `K_{a,b}` with the parts `0..a` and `a..a + b`
+pub fn complete_bipartite(a: usize, b: usize) -> Graph {
+    undirected(a + b, (0..a).flat_map(|i| (a..a + b).map(move |j| (i, j))))
+}
+
+/// ## complete_bipartite_k_paths
+///
+/// number of `k`-paths in `K_{a,b}`. The vertices of a path alternate between both parts, so a path
+/// starting in the part of size `a` uses `ceil(k / 2)` vertices of it and `floor(k / 2)` of the other.
+pub fn complete_bipartite_k_paths(a: usize, b: usize, k: usize) -> u64 {
+    if k == 0 {
+        return 0;
+    }
+    let first = k.div_ceil(2);
+    let second = k / 2;
+    falling_factorial(a, first) * falling_factorial(b, second)
+        + falling_factorial(b, first) * falling_factorial(a, second)
+}
+
+/// ## petersen
+///
+/// the Petersen graph with the outer cycle `0..5`, the spokes `i - (i + 5)` and the inner
+/// pentagram `(5 + i) - (5 + (i + 2) % 5)`
+pub fn petersen() -> Graph {
+    let outer = (0..5).map(|i| (i, (i + 1) % 5));
+    let spokes = (0..5).map(|i| (i, i + 5));
+    let inner = (0..5).map(|i| (5 + i, 5 + (i + 2) % 5));
+    undirected(10, outer.chain(spokes).chain(inner))
+}
+
+/// ## petersen_k_paths
+///
+/// number of `k`-paths in the Petersen graph. Since it is 3-regular with girth 5, there are
+/// `10 * 3 * 2^(k - 2)` paths for `2 <= k <= 5`, the larger values are counted exhaustively.
+pub fn petersen_k_paths(k: usize) -> u64 {
+    match k {
+        1 => 10,
+        2..=5 => 30 << (k - 2),
+        6 => 360,
+        7 => 480,
+        8 => 600,
+        9 => 600,
+        10 => 240,
+        _ => 0,
+    }
+}
+
+/// ## gnp
+///
+/// Erdős–Rényi graph `G(n, p)`: every edge `{i, j}` with `i != j` exists with probability `p`
+pub fn gnp(n: usize, p: f64, seed: u64) -> Graph {
+    assert!((0.0..=1.0).contains(&p), "Probability must be in [0,1]");
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let bernoulli = Bernoulli::new(p).unwrap();
+    let edges: Vec<_> = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .filter(|_| bernoulli.sample(&mut rng))
+        .collect();
+
+    undirected(n, edges)
+}
+
+/// ## gnm
+///
+/// Erdős–Rényi graph `G(n, m)`: a uniformly random graph with `n` vertices and exactly `m` edges
+pub fn gnm(n: usize, m: usize, seed: u64) -> Graph {
+    let max_edges = n * n.saturating_sub(1) / 2;
+    assert!(m <= max_edges, "too many edges for n vertices");
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    // for dense graphs the missing edges are chosen instead
+    let dense = 2 * m > max_edges;
+    let num_chosen = if dense { max_edges - m } else { m };
+
+    let mut chosen = HashSet::with_capacity(num_chosen);
+    while chosen.len() < num_chosen {
+        let i = rng.gen_range(0..n);
+        let j = rng.gen_range(0..n);
+        if i != j {
+            chosen.insert((i.min(j), i.max(j)));
+        }
+    }
+
+    let edges: Vec<_> = if dense {
+        (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|e| !chosen.contains(e))
+            .collect()
+    } else {
+        chosen.into_iter().collect()
+    };
+
+    undirected(n, edges)
+}
+
+/// ## barabasi_albert
+///
+/// Barabási–Albert preferential attachment graph with `n` vertices. It starts with the complete
+/// graph on `m + 1` vertices, every further vertex is connected to `m` distinct existing vertices,
+/// which are chosen with probability proportional to their degree.
+pub fn barabasi_albert(n: usize, m: usize, seed: u64) -> Graph {
+    assert!(m >= 1 && m < n, "m must be in 1..n");
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut edges: Vec<_> = (0..=m)
+        .flat_map(|i| (i + 1..=m).map(move |j| (i, j)))
+        .collect();
+    // every vertex occurs once per incident edge, so a uniform choice is proportional to the degree
+    let mut endpoints: Vec<_> = edges.iter().flat_map(|(i, j)| vec![*i, *j]).collect();
+
+    for v in m + 1..n {
+        let mut targets = HashSet::with_capacity(m);
+        while targets.len() < m {
+            targets.insert(endpoints[rng.gen_range(0..endpoints.len())]);
+        }
+        let mut targets: Vec<_> = targets.into_iter().collect();
+        targets.sort_unstable();
+
+        for t in targets {
+            edges.push((t, v));
+            endpoints.push(t);
+            endpoints.push(v);
+        }
+    }
+
+    undirected(n, edges)
+}
+
+/// ## random_regular
+///
+/// a random `d`-regular graph with `n` vertices, created with the configuration model: `d` copies
+/// of every vertex are matched randomly, until the matching has no self loops and parallel edges.
+/// `n * d` must be even. Only use it for small `d`, since the expected number of tries grows with `d`.
+pub fn random_regular(n: usize, d: usize, seed: u64) -> Graph {
+    assert!(d < n, "d must be smaller than n");
+    assert!((n * d).is_multiple_of(2), "n * d must be even");
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut stubs: Vec<_> = (0..n).flat_map(|v| std::iter::repeat_n(v, d)).collect();
+
+    loop {
+        stubs.shuffle(&mut rng);
+        let mut edges: Vec<_> = stubs
+            .chunks(2)
+            .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+            .collect();
+        edges.sort_unstable();
+
+        let simple = edges.iter().all(|(i, j)| i != j) && edges.windows(2).all(|w| w[0] != w[1]);
+        if simple {
+            return undirected(n, edges);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::generators::*;
+    use crate::graph::Graph;
+
+    fn degrees(g: &Graph) -> Vec<usize> {
+        (0..g.num_vert())
+            .map(|i| g.neighbors_of(i).count())
+            .collect()
+    }
+
+    fn num_edges(g: &Graph) -> usize {
+        degrees(g).iter().sum::<usize>() / 2
+    }
+
+    /// count all `k`-paths by extending every path by one vertex
+    fn count_k_paths(g: &Graph, k: usize) -> u64 {
+        let mut paths: Vec<Vec<usize>> = (0..g.num_vert()).map(|v| vec![v]).collect();
+        for _ in 1..k {
+            paths = paths
+                .into_iter()
+                .flat_map(|p| {
+                    let last = p[p.len() - 1];
+                    g.neighbors_of(last)
+                        .filter(|v| !p.contains(v))
+                        .map(|v| {
+                            let mut q = p.clone();
+                            q.push(v);
+                            q
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+        if k == 0 {
+            0
+        } else {
+            paths.len() as u64
+        }
+    }
+
+    #[test]
+    fn structured() {
+        assert_eq!(
+            complete(5).neighbors_of(2).collect::<Vec<_>>(),
+            vec![0, 1, 3, 4]
+        );
+        assert_eq!(degrees(&path(4)), vec![1, 2, 2, 1]);
+        assert_eq!(degrees(&cycle(5)), vec![2; 5]);
+        assert_eq!(degrees(&star(3)), vec![3, 1, 1, 1]);
+        assert_eq!(degrees(&grid(2, 3)), vec![2, 3, 2, 2, 3, 2]);
+        assert_eq!(degrees(&complete_bipartite(2, 3)), vec![3, 3, 2, 2, 2]);
+        assert_eq!(degrees(&petersen()), vec![3; 10]);
+        assert_eq!(num_edges(&petersen()), 15);
+    }
+
+    #[test]
+    fn complete_graph_file() {
+        let g = complete(10);
+        let expect = Graph::from_graph6("src/data/K10.g6");
+        assert_eq!(g.to_graph6(), expect.to_graph6(), "same graph as K10.g6");
+    }
+
+    #[test]
+    fn closed_form_counts() {
+        for k in 0..=6 {
+            assert_eq!(
+                count_k_paths(&complete(6), k),
+                complete_k_paths(6, k),
+                "K6, k = {}",
+                k
+            );
+            assert_eq!(
+                count_k_paths(&path(5), k),
+                path_k_paths(5, k),
+                "P5, k = {}",
+                k
+            );
+            assert_eq!(
+                count_k_paths(&cycle(5), k),
+                cycle_k_paths(5, k),
+                "C5, k = {}",
+                k
+            );
+            assert_eq!(
+                count_k_paths(&star(4), k),
+                star_k_paths(4, k),
+                "S4, k = {}",
+                k
+            );
+            assert_eq!(
+                count_k_paths(&complete_bipartite(2, 3), k),
+                complete_bipartite_k_paths(2, 3, k),
+                "K2,3, k = {}",
+                k
+            );
+        }
+        for k in 0..=11 {
+            assert_eq!(
+                count_k_paths(&petersen(), k),
+                petersen_k_paths(k),
+                "Petersen, k = {}",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn random() {
+        let g = gnp(30, 0.3, 42);
+        assert_eq!(
+            gnp(30, 0.3, 42).to_graph6(),
+            g.to_graph6(),
+            "same seed, same graph"
+        );
+        assert!(
+            (0..30).all(|i| !g.neighbors_of(i).any(|j| j == i)),
+            "no self loops"
+        );
+        assert_eq!(
+            num_edges(&gnp(10, 1.0, 1)),
+            45,
+            "p = 1 is the complete graph"
+        );
+
+        assert_eq!(num_edges(&gnm(20, 30, 7)), 30, "G(n, m) has m edges");
+        assert_eq!(
+            num_edges(&gnm(20, 180, 7)),
+            180,
+            "dense G(n, m) has m edges"
+        );
+        assert_eq!(gnm(20, 30, 7).to_graph6(), gnm(20, 30, 7).to_graph6());
+
+        let ba = barabasi_albert(50, 2, 3);
+        assert_eq!(num_edges(&ba), 3 + 2 * 47, "every new vertex adds m edges");
+        assert!(degrees(&ba).iter().all(|d| *d >= 2));
+
+        let reg = random_regular(20, 3, 5);
+        assert_eq!(degrees(&reg), vec![3; 20], "every vertex has degree d");
+        assert_eq!(reg.to_graph6(), random_regular(20, 3, 5).to_graph6());
+    }
+}
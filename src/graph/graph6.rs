@@ -0,0 +1,879 @@
+use crate::graph::{error::bad_header, BuildPolicy, Directedness, Graph, GraphBuilder, GraphError};
+use crate::utils::{self, Format};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// # graph6 and sparse6
+///
+/// Readers and writers for the formats of [nauty](https://users.cecs.anu.edu.au/~bdm/data/formats.txt).
+/// graph6 and sparse6 store undirected graphs, digraph6 stores directed graphs. All formats
+/// store graphs as printable ascii characters, where each byte contains 6 bits of data
+/// (the byte value minus 63).
+impl Graph {
+    /// ## from_graph6
+    ///
+    /// create a Graph from a .g6 file which is located at `path_str`.
+    /// The format is detected from the header or the first character, so sparse6 and
+    /// digraph6 files are read as well. Panics if the file can not be read, see `try_from_graph6`.
+    pub fn from_graph6(path_str: &str) -> Self {
+        Graph::try_from_graph6(path_str).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_graph6
+    ///
+    /// create a Graph from a .g6 file which is located at `path_str`, see `from_graph6`
+    pub fn try_from_graph6(path_str: &str) -> Result<Self, GraphError> {
+        Graph::try_from_graph6_with_policy(path_str, BuildPolicy::default())
+    }
+
+    /// ## try_from_graph6_with_policy
+    ///
+    /// create a Graph from a .g6, .s6 or .d6 file which is located at `path_str`, where self loops
+    /// and parallel edges are handled according to `policy`, see `from_graph6`.
+    /// Only sparse6 can store parallel edges, graph6 can not store self loops.
+    pub fn try_from_graph6_with_policy(
+        path_str: &str,
+        policy: BuildPolicy,
+    ) -> Result<Self, GraphError> {
+        let (file, n, format) = utils::file_n_from(path_str)?;
+
+        match format {
+            Format::Graph6 => graph6_graph(n, &file, policy),
+            Format::Sparse6 => Graph::sparse6_file(path_str, policy),
+            Format::Digraph6 => digraph6_graph(n, &file, policy),
+        }
+    }
+
+    /// ## iter_graph6
+    ///
+    /// iterate over all graphs in the file at `path_str`, one graph per line, like the output
+    /// of nauty's `geng`. The file is read line by line, so only one graph is in memory at the
+    /// same time. The file may start with a header, empty lines are skipped and every line can
+    /// be graph6, sparse6 or digraph6. Incremental sparse6 lines (prefixed by `;`) are applied to
+    /// the previous sparse6 graph. Panics if a line can not be read, see `try_iter_graph6`.
+    pub fn iter_graph6(path_str: &str) -> impl Iterator<Item = Graph> {
+        Graph::try_iter_graph6(path_str)
+            .unwrap_or_else(|e| panic!("{}", e))
+            .map(|g| g.unwrap_or_else(|e| panic!("{}", e)))
+    }
+
+    /// ## try_iter_graph6
+    ///
+    /// iterate over all graphs in the file at `path_str`, see `iter_graph6`.
+    /// A line that can not be decoded results in an error, the following lines are still read.
+    pub fn try_iter_graph6(
+        path_str: &str,
+    ) -> Result<impl Iterator<Item = Result<Graph, GraphError>>, GraphError> {
+        let file = File::open(path_str)?;
+        let mut previous = Vec::new();
+
+        Ok(BufReader::new(file)
+            .split(b'\n')
+            .enumerate()
+            .filter_map(move |(i, line)| {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(e) => return Some(Err(GraphError::Io(e))),
+                };
+                let (line, offset) = if i == 0 {
+                    let data = utils::strip_header(&line);
+                    (data, line.len() - data.len())
+                } else {
+                    (&line[..], 0)
+                };
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                if line.is_empty() {
+                    return None;
+                }
+
+                Some(Graph::try_from_line(
+                    line,
+                    &mut previous,
+                    i + 1,
+                    offset,
+                    BuildPolicy::default(),
+                ))
+            }))
+    }
+
+    /// ## try_from_line
+    ///
+    /// decode a single graph6, sparse6 or digraph6 `line` without header and trailing newline.
+    /// `previous` are the edges of the last sparse6 graph as they were decoded (including self
+    /// loops and parallel edges), which are needed for incremental lines. If `line` is a valid
+    /// sparse6 graph, `previous` is replaced by its edges. `line_no` and `offset` (the length of a header in front of `line`) are used for the
+    /// position of an error.
+    fn try_from_line(
+        line: &[u8],
+        previous: &mut Vec<(usize, usize)>,
+        line_no: usize,
+        offset: usize,
+        policy: BuildPolicy,
+    ) -> Result<Self, GraphError> {
+        let (format, n, data) = utils::line_n_from(line, line_no, offset)?;
+
+        match format {
+            Format::Graph6 => graph6_graph(n, data, policy),
+            Format::Digraph6 => digraph6_graph(n, data, policy),
+            Format::Sparse6 => {
+                let mut edges = sparse6_edges(n, data);
+                // incremental lines toggle the edges of the previous graph
+                if line[0] == b';' {
+                    edges = toggle(previous, edges);
+                }
+
+                let g = GraphBuilder::new(n)
+                    .with_directedness(Directedness::Undirected)
+                    .with_policy(policy)
+                    .with_edges(edges.iter().copied())
+                    .try_build()?;
+                *previous = edges;
+                Ok(g)
+            }
+        }
+    }
+
+    /// ## from_digraph6
+    ///
+    /// create a directed Graph from a .d6 file which is located at `path_str`.
+    /// The file may start with a `>>digraph6<<` header, the data starts with `&`.
+    /// Panics if the file can not be read, see `try_from_digraph6`.
+    pub fn from_digraph6(path_str: &str) -> Self {
+        Graph::try_from_digraph6(path_str).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_digraph6
+    ///
+    /// create a directed Graph from a .d6 file which is located at `path_str`, see `from_digraph6`
+    pub fn try_from_digraph6(path_str: &str) -> Result<Self, GraphError> {
+        let (file, n, format) = utils::file_n_from(path_str)?;
+        if format != Format::Digraph6 {
+            return Err(bad_header(1, "digraph6 data must start with '&'"));
+        }
+
+        digraph6_graph(n, &file, BuildPolicy::default())
+    }
+
+    /// ## from_sparse6
+    ///
+    /// create a Graph from a .s6 file which is located at `path_str`.
+    /// The file may start with a `>>sparse6<<` header, only the first graph in the file is read.
+    /// If the graph is stored incrementally (prefixed by `;`), the edges are toggled with respect
+    /// to the empty graph. Panics if the file can not be read, see `try_from_sparse6`.
+    pub fn from_sparse6(path_str: &str) -> Self {
+        Graph::try_from_sparse6(path_str).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_sparse6
+    ///
+    /// create a Graph from a .s6 file which is located at `path_str`, see `from_sparse6`
+    pub fn try_from_sparse6(path_str: &str) -> Result<Self, GraphError> {
+        Graph::sparse6_file(path_str, BuildPolicy::default())
+    }
+
+    /// ## sparse6_file
+    ///
+    /// create a Graph from the first line of the .s6 file at `path_str` with the given `policy`
+    fn sparse6_file(path_str: &str, policy: BuildPolicy) -> Result<Self, GraphError> {
+        let file = std::fs::read(path_str)?;
+        let data = utils::strip_header(&file);
+        let line = data.split(|b| *b == b'\n').next().unwrap_or(&[]);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        Graph::try_from_sparse6_bytes(line, &[], file.len() - data.len(), policy)
+    }
+
+    /// ## from_sparse6_bytes
+    ///
+    /// decode a single sparse6 `line` without the trailing newline. For an incremental line
+    /// (prefixed by `;`) the edges are toggled with respect to the `previous` edges.
+    pub(crate) fn from_sparse6_bytes(line: &[u8], previous: &[(usize, usize)]) -> Self {
+        Graph::try_from_sparse6_bytes(line, previous, 0, BuildPolicy::default())
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_sparse6_bytes
+    ///
+    /// decode a single sparse6 `line` that starts at byte `offset` of the first line of a file,
+    /// see `from_sparse6_bytes`
+    fn try_from_sparse6_bytes(
+        line: &[u8],
+        previous: &[(usize, usize)],
+        offset: usize,
+        policy: BuildPolicy,
+    ) -> Result<Self, GraphError> {
+        match line.first() {
+            Some(b':') | Some(b';') => {
+                Graph::try_from_line(line, &mut previous.to_vec(), 1, offset, policy)
+            }
+            _ => Err(bad_header(1, "sparse6 data must start with ':' or ';'")),
+        }
+    }
+
+    /// ## to_graph6
+    ///
+    /// encode the Graph in the graph6 format, without header and trailing newline.
+    /// The Graph is read as undirected: `{i, j}` is an edge if `j` is a neighbor of `i` or
+    /// `i` is a neighbor of `j`. Self loops can not be stored and are ignored.
+    pub fn to_graph6(&self) -> String {
+        let n = self.num_vert;
+        let edges = self.undirected_edges();
+
+        // bit of position (i, j) with i < j, the upper triangle is stored column wise
+        let mut bits = vec![false; n * n.saturating_sub(1) / 2];
+        for (i, j) in edges {
+            bits[j * (j - 1) / 2 + i] = true;
+        }
+
+        let mut res = utils::n_to(n);
+        res.extend(pack_bits(&bits, false));
+        String::from_utf8(res).expect("graph6 data is ascii")
+    }
+
+    /// ## to_sparse6
+    ///
+    /// encode the Graph in the sparse6 format, without header and trailing newline.
+    /// The Graph is read as undirected like in `to_graph6`, self loops are kept.
+    pub fn to_sparse6(&self) -> String {
+        let n = self.num_vert;
+        let k = sparse6_k(n);
+        let mut edges = self.undirected_edges();
+        edges.extend(
+            (0..n)
+                .filter(|i| self.neighbors_of(*i).any(|j| j == *i))
+                .map(|i| (i, i)),
+        );
+        edges.sort_unstable_by_key(|(i, j)| (*j, *i));
+
+        let mut bits = Vec::new();
+        let mut push = |b: bool, x: usize| {
+            bits.push(b);
+            bits.extend((0..k).rev().map(|shift| (x >> shift) & 1 == 1));
+        };
+
+        let mut v = 0;
+        for (x, j) in edges {
+            if j == v {
+                push(false, x);
+            } else if j == v + 1 {
+                push(true, x);
+                v = j;
+            } else {
+                push(true, j);
+                push(false, x);
+                v = j;
+            }
+        }
+
+        // padding with ones must not be read as an edge to vertex n - 1, see the nauty docs
+        let padding = (6 - bits.len() % 6) % 6;
+        if k < 6 && n == 1 << k && padding > k && v == n - 2 {
+            bits.push(false);
+        }
+
+        let mut res = vec![b':'];
+        res.extend(utils::n_to(n));
+        res.extend(pack_bits(&bits, true));
+        String::from_utf8(res).expect("sparse6 data is ascii")
+    }
+
+    /// ## to_digraph6
+    ///
+    /// encode the Graph in the digraph6 format, without header and trailing newline.
+    /// Every edge is stored with its direction, so an undirected Graph is stored with both directions.
+    pub fn to_digraph6(&self) -> String {
+        let n = self.num_vert;
+
+        // the full adjacency matrix is stored row wise
+        let mut bits = vec![false; n * n];
+        for i in 0..n {
+            for j in self.neighbors_of(i) {
+                bits[i * n + j] = true;
+            }
+        }
+
+        let mut res = vec![b'&'];
+        res.extend(utils::n_to(n));
+        res.extend(pack_bits(&bits, false));
+        String::from_utf8(res).expect("digraph6 data is ascii")
+    }
+
+    /// ## write_graph6
+    ///
+    /// write the Graph to a .g6 file at `path_str`, optionally starting with a `>>graph6<<` header.
+    pub fn write_graph6(&self, path_str: &str, header: bool) -> std::io::Result<()> {
+        write_line(path_str, header.then_some(">>graph6<<"), &self.to_graph6())
+    }
+
+    /// ## write_sparse6
+    ///
+    /// write the Graph to a .s6 file at `path_str`, optionally starting with a `>>sparse6<<` header.
+    pub fn write_sparse6(&self, path_str: &str, header: bool) -> std::io::Result<()> {
+        write_line(
+            path_str,
+            header.then_some(">>sparse6<<"),
+            &self.to_sparse6(),
+        )
+    }
+
+    /// ## write_digraph6
+    ///
+    /// write the Graph to a .d6 file at `path_str`, optionally starting with a `>>digraph6<<` header.
+    pub fn write_digraph6(&self, path_str: &str, header: bool) -> std::io::Result<()> {
+        write_line(
+            path_str,
+            header.then_some(">>digraph6<<"),
+            &self.to_digraph6(),
+        )
+    }
+
+    /// ## undirected_edges
+    ///
+    /// all edges `(i, j)` with `i < j`, where `j` is a neighbor of `i` or the other way round
+    fn undirected_edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<_> = (0..self.num_vert)
+            .flat_map(|i| self.neighbors_of(i).map(move |j| (i.min(j), i.max(j))))
+            .filter(|(i, j)| i != j)
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+}
+
+/// ## sparse6_k
+///
+/// number of bits that are needed to store `n - 1`
+fn sparse6_k(n: usize) -> usize {
+    if n > 1 {
+        (usize::BITS - (n - 1).leading_zeros()) as usize
+    } else {
+        0
+    }
+}
+
+/// ## pack_bits
+///
+/// store 6 `bits` per byte (plus 63), the last byte is padded with `pad`
+fn pack_bits(bits: &[bool], pad: bool) -> Vec<u8> {
+    bits.chunks(6)
+        .map(|chunk| {
+            let v = (0..6).fold(0, |v, i| v << 1 | *chunk.get(i).unwrap_or(&pad) as u8);
+            v + 63
+        })
+        .collect()
+}
+
+/// ## unpack_bits
+///
+/// iterate over the 6 bits (most significant first) stored in every byte of `data`
+fn unpack_bits(data: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    data.iter().flat_map(|b| {
+        let v = b.wrapping_sub(63);
+        (0..6).rev().map(move |shift| (v >> shift) & 1 == 1)
+    })
+}
+
+/// ## graph6_graph
+///
+/// decode the undirected Graph with `n` vertices from the graph6 `data`, which stores the upper
+/// triangle of the adjacency matrix column wise
+fn graph6_graph(n: usize, data: &[u8], policy: BuildPolicy) -> Result<Graph, GraphError> {
+    let positions = (1..n).flat_map(|i| (0..i).map(move |j| (j, i)));
+    let edges = positions
+        .zip(unpack_bits(data))
+        .filter(|(_pos, bit)| *bit)
+        .map(|(pos, _bit)| pos);
+
+    GraphBuilder::new(n)
+        .with_directedness(Directedness::Undirected)
+        .with_policy(policy)
+        .with_edges(edges)
+        .try_build()
+}
+
+/// ## digraph6_graph
+///
+/// decode the directed Graph with `n` vertices from the digraph6 `data`, which stores the full
+/// adjacency matrix row wise
+fn digraph6_graph(n: usize, data: &[u8], policy: BuildPolicy) -> Result<Graph, GraphError> {
+    let positions = (0..n).flat_map(|i| (0..n).map(move |j| (i, j)));
+    let edges = positions
+        .zip(unpack_bits(data))
+        .filter(|(_pos, bit)| *bit)
+        .map(|(pos, _bit)| pos);
+
+    GraphBuilder::new(n)
+        .with_directedness(Directedness::Directed)
+        .with_policy(policy)
+        .with_edges(edges)
+        .try_build()
+}
+
+/// ## write_line
+///
+/// write the optional `header` and `line` followed by a newline to the file at `path_str`
+fn write_line(path_str: &str, header: Option<&str>, line: &str) -> std::io::Result<()> {
+    let mut content = String::from(header.unwrap_or(""));
+    content.push_str(line);
+    content.push('\n');
+    std::fs::write(path_str, content)
+}
+
+/// ## sparse6_edges
+///
+/// decode the edges `(x, v)` with `x <= v` of a graph with `n` vertices from the sparse6 `data`.
+/// The data is a sequence of `(b, x)`, where `b` is one bit and `x` has `k` bits, such that
+/// `n - 1` can be represented with `k` bits. `v` is the current vertex, starting at `0`.
+/// If `b = 1`, `v` is incremented. If `x > v`, then `v` is set to `x`, otherwise `{x, v}` is an edge.
+fn sparse6_edges(n: usize, data: &[u8]) -> Vec<(usize, usize)> {
+    let k = sparse6_k(n);
+
+    let mut bits = unpack_bits(data);
+
+    let mut edges = Vec::new();
+    let mut v = 0;
+    while let Some(b) = bits.next() {
+        let mut x = 0;
+        for _ in 0..k {
+            match bits.next() {
+                Some(bit) => x = x << 1 | bit as usize,
+                // incomplete (x) at the end is padding
+                None => return edges,
+            }
+        }
+
+        if b {
+            v += 1;
+        }
+        if v >= n {
+            break;
+        }
+        if x > v {
+            v = x;
+            // x can be larger than n - 1, which also ends the data
+            if v >= n {
+                break;
+            }
+        } else {
+            edges.push((x, v));
+        }
+    }
+
+    edges
+}
+
+/// ## toggle
+///
+/// toggle the undirected `changes` in the `previous` edges: an edge that occurs an odd number
+/// of times in `changes` is removed (with all of its parallel edges) if it is in `previous` and
+/// added otherwise. All other edges of `previous` are kept, including self loops and parallel
+/// edges. The result is sorted.
+fn toggle(previous: &[(usize, usize)], changes: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    let normalize = |(i, j): (usize, usize)| (i.min(j), i.max(j));
+    let mut changes: Vec<_> = changes.into_iter().map(normalize).collect();
+    changes.sort_unstable();
+    let mut toggled: Vec<(usize, usize)> = Vec::new();
+    for e in changes {
+        if toggled.last() == Some(&e) {
+            toggled.pop();
+        } else {
+            toggled.push(e);
+        }
+    }
+
+    let mut res: Vec<_> = previous.iter().copied().map(normalize).collect();
+    res.sort_unstable();
+    let mut added = Vec::new();
+    for e in toggled {
+        let start = res.partition_point(|f| *f < e);
+        let end = res.partition_point(|f| *f <= e);
+        if start < end {
+            res.drain(start..end);
+        } else {
+            added.push(e);
+        }
+    }
+    res.extend(added);
+    res.sort_unstable();
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::{from_content, neighbors, TempFile};
+    use crate::graph::{Directedness, Graph, GraphError};
+
+    #[test]
+    fn sparse6() {
+        let g = Graph::from_sparse6("src/data/path10.s6");
+        let expect = Graph::from_graph6("src/data/path10.g6");
+        assert_eq!(g.num_vert(), 10);
+        assert_eq!(
+            neighbors(&g),
+            neighbors(&expect),
+            "10 path graph from sparse6"
+        );
+    }
+
+    #[test]
+    fn sparse6_header() {
+        let g = Graph::from_sparse6("src/data/path10_with_header.s6");
+        let expect = Graph::from_graph6("src/data/path10.g6");
+        assert_eq!(
+            neighbors(&g),
+            neighbors(&expect),
+            "10 path graph from sparse6 with header"
+        );
+    }
+
+    #[test]
+    fn sparse6_big() {
+        let g = Graph::from_sparse6("src/data/path100.s6");
+        let expect = Graph::from_graph6("src/data/path100.g6");
+        assert_eq!(g.num_vert(), 100);
+        assert_eq!(
+            neighbors(&g),
+            neighbors(&expect),
+            "100 path graph from sparse6"
+        );
+    }
+
+    #[test]
+    fn sparse6_example() {
+        // example from the nauty documentation: edges 0-1, 0-2, 1-2 and 5-6
+        let g = Graph::from_sparse6_bytes(b":Fa@x^", &[]);
+        let expect = vec![
+            vec![1, 2],
+            vec![0, 2],
+            vec![0, 1],
+            vec![],
+            vec![],
+            vec![6],
+            vec![5],
+        ];
+        assert_eq!(neighbors(&g), expect, "sparse6 example");
+    }
+
+    #[test]
+    fn sparse6_incremental() {
+        let g = Graph::from_sparse6_bytes(b";Fa@x^", &[(0, 1), (3, 4)]);
+        let expect = vec![
+            vec![2],
+            vec![2],
+            vec![0, 1],
+            vec![4],
+            vec![3],
+            vec![6],
+            vec![5],
+        ];
+        assert_eq!(neighbors(&g), expect, "edges are toggled");
+    }
+
+    #[test]
+    fn sparse6_incremental_self_loop() {
+        // a self loop at 0 and the edge 0 - 1, then the edge 0 - 1 is toggled twice
+        let file = TempFile::with_content("incremental_loop", ":AJ\n;An\n;An\n");
+
+        let graphs: Vec<_> = Graph::iter_graph6(file.path_str()).collect();
+        assert_eq!(
+            neighbors(&graphs[0]),
+            vec![vec![0, 1], vec![0]],
+            "first graph"
+        );
+        assert_eq!(
+            neighbors(&graphs[1]),
+            vec![vec![0], vec![]],
+            "self loop is kept while the edge is toggled"
+        );
+        assert_eq!(
+            neighbors(&graphs[2]),
+            vec![vec![0, 1], vec![0]],
+            "self loop of the previous graph is kept"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "could not read file")]
+    fn sparse6_not_found() {
+        Graph::from_sparse6("src/data/this_is_not_a_file.s6");
+    }
+
+    /// content of the first graph in a fixture, without header and newline
+    fn fixture(path: &str, header: &str) -> String {
+        let content = std::fs::read_to_string(path).unwrap();
+        let content = content.strip_prefix(header).unwrap_or(&content);
+        content.lines().next().unwrap().to_string()
+    }
+
+    #[test]
+    fn graph6_round_trip() {
+        for name in [
+            "K4",
+            "K5",
+            "K10",
+            "K20",
+            "K80",
+            "path3",
+            "path4",
+            "path6",
+            "path10",
+            "path100",
+            "tutte_graph",
+        ]
+        .iter()
+        {
+            let path = format!("src/data/{}.g6", name);
+            let g = Graph::from_graph6(&path);
+            assert_eq!(
+                g.to_graph6(),
+                fixture(&path, ">>graph6<<"),
+                "{} should be written like the fixture",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn sparse6_round_trip() {
+        for name in ["path10", "path100"].iter() {
+            let path = format!("src/data/{}.s6", name);
+            let g = Graph::from_sparse6(&path);
+            assert_eq!(
+                g.to_sparse6(),
+                fixture(&path, ">>sparse6<<"),
+                "{} should be written like the fixture",
+                name
+            );
+        }
+
+        for name in ["K4", "K5", "K20", "path3", "path4", "tutte_graph"].iter() {
+            let g = Graph::from_graph6(&format!("src/data/{}.g6", name));
+            let s6 = g.to_sparse6();
+            let read = Graph::from_sparse6_bytes(s6.as_bytes(), &[]);
+            assert_eq!(neighbors(&read), neighbors(&g), "{} round trip", name);
+        }
+    }
+
+    #[test]
+    fn sparse6_padding() {
+        // triangle on 4 vertices, padding with ones only would add the self loop 3-3
+        let g = Graph::from_edges(4, vec![(0, 1), (1, 2), (0, 2)]);
+        assert_eq!(g.to_sparse6(), ":CcJ");
+
+        let read = Graph::from_sparse6_bytes(b":CcJ", &[]);
+        assert_eq!(
+            neighbors(&read),
+            vec![vec![1, 2], vec![0, 2], vec![0, 1], vec![]],
+            "no edge at the last vertex"
+        );
+    }
+
+    #[test]
+    fn write_files() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        let file = TempFile::new("g6");
+        let path_str = file.path_str();
+
+        g.write_graph6(path_str, true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            std::fs::read_to_string("src/data/path10_with_header.g6").unwrap(),
+            "graph6 file with header"
+        );
+        g.write_sparse6(path_str, false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            std::fs::read_to_string("src/data/path10.s6").unwrap(),
+            "sparse6 file without header"
+        );
+    }
+
+    #[test]
+    fn digraph6() {
+        // example from the nauty documentation: edges 0->2, 0->4, 3->1 and 3->4
+        let expect = vec![vec![2, 4], vec![], vec![], vec![1, 4], vec![]];
+        for path in ["src/data/digraph5.d6", "src/data/digraph5_with_header.d6"].iter() {
+            let g = Graph::from_digraph6(path);
+            assert_eq!(g.directedness(), Directedness::Directed);
+            assert_eq!(neighbors(&g), expect, "digraph6 example");
+            assert_eq!(g.to_digraph6(), "&DI?AO?", "digraph6 round trip");
+        }
+    }
+
+    #[test]
+    fn detect_format() {
+        let g = Graph::from_graph6("src/data/digraph5_with_header.d6");
+        assert_eq!(
+            g.directedness(),
+            Directedness::Directed,
+            "digraph6 is detected"
+        );
+
+        let g = Graph::from_graph6("src/data/path10_with_header.s6");
+        let expect = Graph::from_graph6("src/data/path10.g6");
+        assert_eq!(neighbors(&g), neighbors(&expect), "sparse6 is detected");
+    }
+
+    #[test]
+    #[should_panic(expected = "digraph6 data must start with '&'")]
+    fn digraph6_wrong_format() {
+        Graph::from_digraph6("src/data/path10.g6");
+    }
+
+    #[test]
+    fn write_digraph6() {
+        let g = Graph::from_digraph6("src/data/digraph5.d6");
+        let file = TempFile::new("d6");
+        let path_str = file.path_str();
+
+        g.write_digraph6(path_str, true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            std::fs::read_to_string("src/data/digraph5_with_header.d6").unwrap(),
+            "digraph6 file with header"
+        );
+    }
+
+    #[test]
+    fn iter_graph6() {
+        // all graphs with 3 vertices, as written by geng
+        let graphs: Vec<_> = Graph::iter_graph6("src/data/geng3.g6").collect();
+        assert_eq!(graphs.len(), 4, "one graph per line");
+
+        let expect = vec![
+            vec![vec![], vec![], vec![]],
+            vec![vec![2], vec![], vec![0]],
+            vec![vec![2], vec![2], vec![0, 1]],
+            vec![vec![1, 2], vec![0, 2], vec![0, 1]],
+        ];
+        for (g, e) in graphs.iter().zip(expect) {
+            assert_eq!(neighbors(g), e, "graphs are read in order");
+        }
+    }
+
+    #[test]
+    fn iter_sparse6() {
+        let file = TempFile::with_content("iter", ">>sparse6<<:Fa@x^\r\n;Fa@x^\n\n&DI?AO?\n");
+
+        let graphs: Vec<_> = Graph::iter_graph6(file.path_str()).collect();
+        assert_eq!(graphs.len(), 3, "empty lines are skipped");
+        assert_eq!(neighbors(&graphs[0])[5], vec![6], "first graph");
+        assert_eq!(
+            neighbors(&graphs[1]),
+            vec![vec![]; 7],
+            "incremental graph toggles all edges of the previous graph"
+        );
+        assert_eq!(
+            graphs[2].directedness(),
+            Directedness::Directed,
+            "digraph6 line"
+        );
+    }
+
+    #[test]
+    // the dense adjacency matrix would need n * n bytes
+    #[cfg(not(feature = "matrix_naive"))]
+    fn sparse6_huge_n() {
+        // n >= 258048 needs the 8 byte size encoding
+        let n = 300_000;
+        let g = Graph::from_edges(n, vec![(0, 1), (n - 2, n - 1)]);
+        let s6 = g.to_sparse6();
+        assert!(s6.starts_with(":~~"), "8 byte size encoding");
+
+        let read = Graph::from_sparse6_bytes(s6.as_bytes(), &[]);
+        assert_eq!(read.num_vert(), n);
+        assert_eq!(read.neighbors_of(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(read.neighbors_of(n - 1).collect::<Vec<_>>(), vec![n - 2]);
+    }
+
+    fn try_from_content(content: &[u8]) -> Result<Graph, GraphError> {
+        from_content("g6_err", content, Graph::try_from_graph6)
+    }
+
+    #[test]
+    fn errors() {
+        assert!(
+            matches!(
+                Graph::try_from_graph6("src/data/this_is_not_a_file.g6"),
+                Err(GraphError::Io(_))
+            ),
+            "missing file"
+        );
+        assert!(
+            matches!(
+                try_from_content(b""),
+                Err(GraphError::Truncated { line: 1, byte: 0 })
+            ),
+            "empty file"
+        );
+        assert!(
+            matches!(
+                try_from_content(b">>graph6<<IhC\n"),
+                Err(GraphError::Truncated { line: 1, byte: 13 })
+            ),
+            "10 vertices need 8 bytes of data"
+        );
+        assert!(
+            matches!(
+                try_from_content(b">>graph6<<~?@"),
+                Err(GraphError::Truncated { line: 1, byte: 13 })
+            ),
+            "truncated size"
+        );
+        assert!(
+            matches!(
+                try_from_content(b"Ch a\n"),
+                Err(GraphError::BadChar {
+                    line: 1,
+                    byte: 2,
+                    found: ' '
+                })
+            ),
+            "bad character"
+        );
+        assert!(
+            matches!(
+                try_from_content(b">>sparse6<<:Fa@\tx^"),
+                Err(GraphError::BadChar {
+                    line: 1,
+                    byte: 15,
+                    ..
+                })
+            ),
+            "bad character in sparse6"
+        );
+        assert!(
+            matches!(
+                Graph::try_from_digraph6("src/data/path10.g6"),
+                Err(GraphError::BadHeader { line: 1, .. })
+            ),
+            "graph6 is not digraph6"
+        );
+        for content in [&b"&~~~~~~~~~"[..], b"~~~~~~~~"].iter() {
+            assert!(
+                matches!(
+                    try_from_content(content),
+                    Err(GraphError::BadHeader { line: 1, .. })
+                ),
+                "number of bits of 2^36 - 1 vertices overflows"
+            );
+        }
+    }
+
+    #[test]
+    fn iter_errors() {
+        let file = TempFile::with_content("iter_err", "B?\nB\nBw\n");
+
+        let graphs: Vec<_> = Graph::try_iter_graph6(file.path_str()).unwrap().collect();
+
+        assert_eq!(graphs.len(), 3);
+        assert!(graphs[0].is_ok());
+        assert!(
+            matches!(graphs[1], Err(GraphError::Truncated { line: 2, byte: 1 })),
+            "error has the line of the graph"
+        );
+        assert!(graphs[2].is_ok(), "following lines are still read");
+    }
+}
@@ -0,0 +1,319 @@
+use crate::graph::error::{bad_header, columns, parse_token};
+use crate::graph::{BuildPolicy, Directedness, Graph, GraphBuilder, GraphError};
+
+/// # KonectFormat
+///
+/// The network format given in the first header line of a KONECT file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KonectFormat {
+    /// undirected network, every edge is listed once
+    Sym,
+    /// directed network
+    Asym,
+    /// undirected bipartite network, the two columns index two different sets of vertices
+    Bip,
+}
+
+/// # EdgeData
+///
+/// The optional columns of an edge in a KONECT file. `from` and `to` are the vertex indices in
+/// the Graph (starting at `0`). If the file has no weight column, the weight is `1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeData {
+    pub from: usize,
+    pub to: usize,
+    pub weight: f64,
+    pub timestamp: Option<u64>,
+}
+
+/// # KONECT
+///
+/// Reader for the tsv files of the [KONECT](http://konect.cc) network collection.
+impl Graph {
+    /// ## from_tsv
+    ///
+    /// Create a Graph from the given `path_str` KONECT tsv file.
+    /// The first line has to be `% format weights`, where format is `sym`, `asym` or `bip`.
+    /// The second line should be `% num_edges n1 n2`. If it is missing, the number of vertices
+    /// is the largest index in the file.
+    /// All lines that are not prefixed by % are assumend to have the following format:
+    /// `from_id to_id [weight [timestamp]]` (indices start at `1`).
+    ///
+    /// `sym` networks are undirected and `asym` networks directed. A `bip` network has
+    /// `n1 + n2` vertices, where the vertex `to_id` of the second set becomes `n1 + to_id`,
+    /// and is undirected. Every line of the file is kept in `edge_data`, also if an edge
+    /// occurs multiple times. Panics if the file is invalid, see `try_from_tsv`.
+    pub fn from_tsv(path_str: &str) -> Self {
+        Graph::try_from_tsv(path_str).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_tsv
+    ///
+    /// Create a Graph from the given `path_str` KONECT tsv file, see `from_tsv`
+    pub fn try_from_tsv(path_str: &str) -> Result<Self, GraphError> {
+        Graph::try_from_tsv_with_policy(path_str, BuildPolicy::default())
+    }
+
+    /// ## try_from_tsv_with_policy
+    ///
+    /// Create a Graph from the given `path_str` KONECT tsv file, where self loops and parallel
+    /// edges are handled according to `policy`, see `from_tsv`. `edge_data` still contains every
+    /// line of the file
+    pub fn try_from_tsv_with_policy(
+        path_str: &str,
+        policy: BuildPolicy,
+    ) -> Result<Self, GraphError> {
+        let file = std::fs::read_to_string(path_str)?;
+        let mut lines = file
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .peekable();
+
+        let format = match lines
+            .next()
+            .and_then(|(_i, line)| line.strip_prefix('%'))
+            .and_then(|line| line.split_whitespace().next())
+        {
+            Some("sym") => KonectFormat::Sym,
+            Some("asym") => KonectFormat::Asym,
+            Some("bip") => KonectFormat::Bip,
+            _ => {
+                return Err(bad_header(
+                    1,
+                    "KONECT header must start with % sym, % asym or % bip",
+                ))
+            }
+        };
+
+        let mut dim = Vec::new();
+        if let Some((i, line)) = lines.peek().copied() {
+            if let Some(sizes) = line.strip_prefix('%') {
+                lines.next();
+                for v in sizes.split_whitespace() {
+                    dim.push(parse_token::<usize>(line, v, i)?);
+                }
+            }
+        }
+
+        let mut edge_data = Vec::new();
+        let mut line_of_edge = Vec::new();
+        for (i, line) in
+            lines.filter(|(_i, line)| !line.starts_with('%') && !line.trim().is_empty())
+        {
+            let columns = columns(line, i, 2)?;
+            let from = parse_token::<usize>(line, columns[0], i)?;
+            let to = parse_token::<usize>(line, columns[1], i)?;
+            if from == 0 || to == 0 {
+                return Err(GraphError::SizeMismatch {
+                    line: i,
+                    message: String::from("vertex index out of bounds"),
+                    expected: 1,
+                    found: 0,
+                });
+            }
+
+            edge_data.push(EdgeData {
+                from: from - 1,
+                to: to - 1,
+                weight: match columns.get(2) {
+                    Some(w) => parse_token(line, w, i)?,
+                    None => 1.,
+                },
+                timestamp: match columns.get(3) {
+                    Some(t) => Some(parse_token(line, t, i)?),
+                    None => None,
+                },
+            });
+            line_of_edge.push(i);
+        }
+
+        // without a size line, the largest indices determine the number of vertices
+        let max_from = edge_data.iter().map(|e| e.from + 1).max().unwrap_or(0);
+        let max_to = edge_data.iter().map(|e| e.to + 1).max().unwrap_or(0);
+        let (n1, n2) = match dim[..] {
+            [_num_edges, n1, n2, ..] => (n1, n2),
+            _ => (max_from, max_to),
+        };
+
+        let (num_vert, directedness, offset) = match format {
+            KonectFormat::Sym => (n1.max(n2), Directedness::Undirected, 0),
+            KonectFormat::Asym => (n1.max(n2), Directedness::Directed, 0),
+            KonectFormat::Bip => (n1 + n2, Directedness::Undirected, n1),
+        };
+        let (from_bound, to_bound) = match format {
+            KonectFormat::Bip => (n1, n2),
+            _ => (num_vert, num_vert),
+        };
+
+        for (e, line) in edge_data.iter_mut().zip(line_of_edge) {
+            for (v, bound) in [(e.from, from_bound), (e.to, to_bound)].iter() {
+                if v >= bound {
+                    return Err(GraphError::SizeMismatch {
+                        line,
+                        message: String::from("vertex index out of bounds"),
+                        expected: *bound,
+                        found: v + 1,
+                    });
+                }
+            }
+            e.to += offset;
+        }
+
+        let mut g = GraphBuilder::new(num_vert)
+            .with_directedness(directedness)
+            .with_policy(policy)
+            .with_edges(edge_data.iter().map(|e| (e.from, e.to)))
+            .try_build()?;
+        g.edge_data = edge_data;
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::TempFile;
+    use crate::graph::{BuildPolicy, Directedness, EdgeData, EdgePolicy, Graph, GraphError};
+
+    #[test]
+    fn sym() {
+        let g = Graph::from_tsv("src/data/out.moreno_kangaroo_kangaroo");
+        assert_eq!(g.num_vert(), 17);
+        assert_eq!(g.directedness(), Directedness::Undirected);
+        assert!(
+            g.neighbors_of(1).any(|j| j == 0),
+            "undirected networks are symmetrized"
+        );
+        assert_eq!(g.edge_data.len(), 91, "every line is kept");
+        assert_eq!(
+            g.edge_data[0],
+            EdgeData {
+                from: 0,
+                to: 1,
+                weight: 21.,
+                timestamp: None
+            },
+            "weights are kept"
+        );
+    }
+
+    #[test]
+    fn asym() {
+        let g = Graph::from_tsv("src/data/out.moreno_innovation_innovation");
+        assert_eq!(g.num_vert(), 241);
+        assert_eq!(g.directedness(), Directedness::Directed);
+        assert!(g.neighbors_of(0).any(|j| j == 1));
+        assert!(
+            !g.neighbors_of(1).any(|j| j == 0),
+            "directed networks are not symmetrized"
+        );
+        assert_eq!(g.edge_data[0].weight, 1., "unweighted edges have weight 1");
+    }
+
+    #[test]
+    fn bip_temporal() {
+        let g = Graph::from_tsv("src/data/out.edit-sewikibooks");
+        assert_eq!(g.num_vert(), 162, "bipartite graph has n1 + n2 vertices");
+        assert_eq!(
+            g.edge_data[0],
+            EdgeData {
+                from: 0,
+                to: 81,
+                weight: 1.,
+                timestamp: Some(1107024175)
+            },
+            "second vertex set starts at n1, timestamps are kept"
+        );
+        assert!(
+            g.neighbors_of(81).any(|j| j == 0),
+            "bipartite edges are undirected"
+        );
+    }
+
+    #[test]
+    fn without_size() {
+        let file = TempFile::new("konect");
+        file.write("% asym unweighted\n1 2\n3 1\n");
+
+        let g = Graph::from_tsv(file.path_str());
+        assert_eq!(g.num_vert(), 3, "largest index is the number of vertices");
+        assert_eq!(g.neighbors_of(2).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "KONECT header must start with % sym, % asym or % bip")]
+    fn bad_header() {
+        Graph::from_tsv("src/data/path10.g6");
+    }
+
+    #[test]
+    fn errors() {
+        let file = TempFile::new("konect_err");
+        let path_str = file.path_str();
+
+        file.write("% bip unweighted\n% 2 2 3\n1 3\n3 1\n");
+        assert!(
+            matches!(
+                Graph::try_from_tsv(path_str),
+                Err(GraphError::SizeMismatch {
+                    line: 4,
+                    expected: 2,
+                    found: 3,
+                    ..
+                })
+            ),
+            "vertex of the first set is out of bounds"
+        );
+
+        file.write("% sym weighted\n1 2 x\n");
+        assert!(
+            matches!(
+                Graph::try_from_tsv(path_str),
+                Err(GraphError::BadChar {
+                    line: 2,
+                    byte: 4,
+                    found: 'x'
+                })
+            ),
+            "weight is not a number"
+        );
+
+        file.write("% sym unweighted\n1\n");
+        assert!(
+            matches!(
+                Graph::try_from_tsv(path_str),
+                Err(GraphError::Truncated { line: 2, byte: 1 })
+            ),
+            "second vertex is missing"
+        );
+    }
+
+    #[test]
+    fn policy() {
+        let file = TempFile::new("konect_policy");
+        let path_str = file.path_str();
+        file.write("% asym unweighted\n1 2\n2 2\n1 2\n2 3\n");
+
+        let g = Graph::from_tsv(path_str);
+        assert_eq!(
+            g.dropped_edges().parallel_edges,
+            1,
+            "repeated edge is dropped"
+        );
+        assert_eq!(g.edge_data.len(), 4, "every line is kept in edge_data");
+
+        let policy = BuildPolicy::new().with_self_loops(EdgePolicy::Drop);
+        let g = Graph::try_from_tsv_with_policy(path_str, policy).unwrap();
+        assert_eq!(g.dropped_edges().self_loops, 1, "self loop is dropped");
+        assert_eq!(g.neighbors_of(1).collect::<Vec<_>>(), vec![2]);
+
+        let policy = BuildPolicy::new().with_parallel_edges(EdgePolicy::Reject);
+        assert!(
+            matches!(
+                Graph::try_from_tsv_with_policy(path_str, policy),
+                Err(GraphError::Rejected { from: 0, to: 1, .. })
+            ),
+            "repeated edge is rejected"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::graph::error::{bad_header, columns, parse_token, parse_vertex};
+use crate::graph::{BuildPolicy, Directedness, EdgeData, Graph, GraphBuilder, GraphError};
+use std::collections::HashMap;
+
+/// # Matrix Market
+///
+/// Reader and writer for [Matrix Market](https://math.nist.gov/MatrixMarket/formats.html)
+/// coordinate files. Every non zero entry `(i, j)` is an edge from vertex `i` to vertex `j`.
+impl Graph {
+    /// ## from_matrix_market
+    ///
+    /// Create a Graph from the .mtx file at `path_str`. The first line has to be
+    /// `%%MatrixMarket matrix coordinate field symmetry`, where field is `pattern`, `integer`
+    /// or `real` and symmetry is `general` or `symmetric`. It is followed by comments
+    /// (prefixed by %), the line `nrows ncols nnz` and `nnz` lines `row col [value]`
+    /// (indices start at `1`).
+    ///
+    /// `general` matrices are directed graphs, `symmetric` matrices (which only store the lower
+    /// triangle) are undirected graphs. Entries with value zero are not edges, the values of all
+    /// other entries are kept as weights in `edge_data`.
+    /// The entries are collected directly into the sparse adjacency matrix.
+    /// Panics if the file is invalid, see `try_from_matrix_market`.
+    pub fn from_matrix_market(path_str: &str) -> Self {
+        Graph::try_from_matrix_market(path_str).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_matrix_market
+    ///
+    /// Create a Graph from the .mtx file at `path_str`, see `from_matrix_market`
+    pub fn try_from_matrix_market(path_str: &str) -> Result<Self, GraphError> {
+        Graph::try_from_matrix_market_with_policy(path_str, BuildPolicy::default())
+    }
+
+    /// ## try_from_matrix_market_with_policy
+    ///
+    /// Create a Graph from the .mtx file at `path_str`, where self loops and parallel edges are
+    /// handled according to `policy`, see `from_matrix_market`. `edge_data` still contains every
+    /// entry of the file
+    pub fn try_from_matrix_market_with_policy(
+        path_str: &str,
+        policy: BuildPolicy,
+    ) -> Result<Self, GraphError> {
+        let file = std::fs::read_to_string(path_str)?;
+        let mut lines = file.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        let header: Vec<_> = lines
+            .next()
+            .map_or("", |(_i, line)| line)
+            .split_whitespace()
+            .map(|v| v.to_lowercase())
+            .collect();
+        if header.len() != 5 || header[0] != "%%matrixmarket" || header[1] != "matrix" {
+            return Err(bad_header(
+                1,
+                "Matrix Market header must be %%MatrixMarket matrix coordinate field symmetry",
+            ));
+        }
+        if header[2] != "coordinate" {
+            return Err(bad_header(
+                1,
+                "only coordinate Matrix Market files are supported",
+            ));
+        }
+        let has_value = match header[3].as_str() {
+            "pattern" => false,
+            "integer" | "real" => true,
+            _ => {
+                return Err(bad_header(
+                    1,
+                    "Matrix Market field must be pattern, integer or real",
+                ))
+            }
+        };
+        let directedness = match header[4].as_str() {
+            "general" => Directedness::Directed,
+            "symmetric" => Directedness::Undirected,
+            _ => {
+                return Err(bad_header(
+                    1,
+                    "Matrix Market symmetry must be general or symmetric",
+                ))
+            }
+        };
+
+        let mut lines =
+            lines.filter(|(_i, line)| !line.starts_with('%') && !line.trim().is_empty());
+        let (size_line, line) = lines
+            .next()
+            .ok_or_else(|| bad_header(1, "Matrix Market size line is missing"))?;
+        let size = columns(line, size_line, 3)?;
+        let nrows: usize = parse_token(line, size[0], size_line)?;
+        let ncols: usize = parse_token(line, size[1], size_line)?;
+        let nnz: usize = parse_token(line, size[2], size_line)?;
+        if nrows != ncols {
+            return Err(GraphError::SizeMismatch {
+                line: size_line,
+                message: String::from("adjacency matrix must be square"),
+                expected: nrows,
+                found: ncols,
+            });
+        }
+
+        let mut edge_data = Vec::with_capacity(nnz);
+        let mut num_entries = 0;
+        for (i, line) in lines {
+            let columns = columns(line, i, if has_value { 3 } else { 2 })?;
+            let from = parse_vertex(line, columns[0], i, nrows)?;
+            let to = parse_vertex(line, columns[1], i, ncols)?;
+            let weight = if has_value {
+                parse_token(line, columns[2], i)?
+            } else {
+                1.
+            };
+
+            num_entries += 1;
+            if weight != 0. {
+                edge_data.push(EdgeData {
+                    from,
+                    to,
+                    weight,
+                    timestamp: None,
+                });
+            }
+        }
+
+        if num_entries != nnz {
+            return Err(GraphError::SizeMismatch {
+                line: size_line,
+                message: String::from(
+                    "number of Matrix Market entries does not match the size line",
+                ),
+                expected: nnz,
+                found: num_entries,
+            });
+        }
+
+        let mut g = GraphBuilder::new(nrows)
+            .with_directedness(directedness)
+            .with_policy(policy)
+            .with_edges(edge_data.iter().map(|e| (e.from, e.to)))
+            .try_build()?;
+        g.edge_data = edge_data;
+        Ok(g)
+    }
+
+    /// ## to_matrix_market
+    ///
+    /// Encode the adjacency matrix as a Matrix Market file. A directed Graph is written
+    /// as a `general` matrix and an undirected Graph as a `symmetric` matrix, where only the
+    /// entries `(i, j)` with `i >= j` are stored.
+    /// If any weight in `edge_data` is not `1`, the weights are written as a `real` matrix, where
+    /// edges without data get the weight `1`. Otherwise the file is a `pattern` matrix.
+    pub fn to_matrix_market(&self) -> String {
+        let undirected = self.directedness == Directedness::Undirected;
+        let entries: Vec<_> = (0..self.num_vert)
+            .flat_map(|i| self.neighbors_of(i).map(move |j| (i, j)))
+            .filter(|(i, j)| !undirected || i >= j)
+            .collect();
+
+        // the weight of an undirected edge can be given in either direction
+        let mut weights = HashMap::new();
+        for e in self.edge_data.iter() {
+            weights.insert((e.from, e.to), e.weight);
+            if undirected {
+                weights.insert((e.to, e.from), e.weight);
+            }
+        }
+
+        let weighted = self.edge_data.iter().any(|e| e.weight != 1.);
+        let field = if weighted { "real" } else { "pattern" };
+        let symmetry = if undirected { "symmetric" } else { "general" };
+        let mut res = format!("%%MatrixMarket matrix coordinate {} {}\n", field, symmetry);
+        res.push_str(&format!(
+            "{} {} {}\n",
+            self.num_vert,
+            self.num_vert,
+            entries.len()
+        ));
+        for (i, j) in entries {
+            if weighted {
+                let weight = weights.get(&(i, j)).unwrap_or(&1.);
+                res.push_str(&format!("{} {} {}\n", i + 1, j + 1, weight));
+            } else {
+                res.push_str(&format!("{} {}\n", i + 1, j + 1));
+            }
+        }
+
+        res
+    }
+
+    /// ## write_matrix_market
+    ///
+    /// write the Graph to a .mtx file at `path_str`, see `to_matrix_market`
+    pub fn write_matrix_market(&self, path_str: &str) -> std::io::Result<()> {
+        std::fs::write(path_str, self.to_matrix_market())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::{neighbors, TempFile};
+    use crate::graph::{Directedness, Graph, GraphError};
+
+    #[test]
+    fn symmetric_pattern() {
+        let g = Graph::from_matrix_market("src/data/path10.mtx");
+        let expect = Graph::from_graph6("src/data/path10.g6");
+        assert_eq!(g.directedness(), Directedness::Undirected);
+        assert_eq!(neighbors(&g), neighbors(&expect), "10 path graph from mtx");
+    }
+
+    #[test]
+    fn general_real() {
+        let g = Graph::from_matrix_market("src/data/digraph5.mtx");
+        let expect = Graph::from_digraph6("src/data/digraph5.d6");
+        assert_eq!(g.directedness(), Directedness::Directed);
+        assert_eq!(
+            neighbors(&g),
+            neighbors(&expect),
+            "zero entries are no edges"
+        );
+        let weights: Vec<_> = g.edge_data.iter().map(|e| e.weight).collect();
+        assert_eq!(weights, vec![1.5, 2., -1., 0.25], "values are kept");
+    }
+
+    #[test]
+    fn round_trip() {
+        let g = Graph::from_matrix_market("src/data/path10.mtx");
+        assert_eq!(
+            g.to_matrix_market(),
+            std::fs::read_to_string("src/data/path10.mtx").unwrap(),
+            "undirected graph is written as symmetric matrix"
+        );
+
+        let g = Graph::from_digraph6("src/data/digraph5.d6");
+        let file = TempFile::new("mtx");
+        g.write_matrix_market(file.path_str()).unwrap();
+        let read = Graph::from_matrix_market(file.path_str());
+        assert_eq!(read.directedness(), Directedness::Directed);
+        assert_eq!(neighbors(&read), neighbors(&g), "directed round trip");
+    }
+
+    #[test]
+    fn round_trip_weights() {
+        let g = Graph::from_matrix_market("src/data/digraph5.mtx");
+        let file = TempFile::new("mtx_weights");
+        g.write_matrix_market(file.path_str()).unwrap();
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        assert!(
+            content.starts_with("%%MatrixMarket matrix coordinate real general\n"),
+            "weights are written as real matrix"
+        );
+
+        let read = Graph::from_matrix_market(file.path_str());
+        assert_eq!(neighbors(&read), neighbors(&g), "weighted round trip");
+        assert_eq!(read.edge_data, g.edge_data, "weights are kept");
+
+        // the weights of an undirected graph are stored with (i, j) and i < j
+        let mut g = Graph::from_matrix_market("src/data/path10.mtx");
+        for e in g.edge_data.iter_mut() {
+            e.weight = (e.from + e.to) as f64 / 2.;
+        }
+        g.write_matrix_market(file.path_str()).unwrap();
+        let read = Graph::from_matrix_market(file.path_str());
+        let weight_of = |g: &Graph, i: usize, j: usize| {
+            g.edge_data
+                .iter()
+                .find(|e| (e.from, e.to) == (i, j) || (e.from, e.to) == (j, i))
+                .map(|e| e.weight)
+        };
+        for i in 0..9 {
+            assert_eq!(
+                weight_of(&read, i, i + 1),
+                weight_of(&g, i, i + 1),
+                "undirected weights are kept"
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let file = TempFile::new("mtx_err");
+        let path_str = file.path_str();
+
+        file.write("%%MatrixMarket matrix coordinate pattern general\n2 3 1\n1 3\n");
+        assert!(
+            matches!(
+                Graph::try_from_matrix_market(path_str),
+                Err(GraphError::SizeMismatch {
+                    line: 2,
+                    expected: 2,
+                    found: 3,
+                    ..
+                })
+            ),
+            "adjacency matrix must be square"
+        );
+
+        file.write("%%MatrixMarket matrix coordinate pattern general\n2 2 2\n1 2\n");
+        assert!(
+            matches!(
+                Graph::try_from_matrix_market(path_str),
+                Err(GraphError::SizeMismatch {
+                    expected: 2,
+                    found: 1,
+                    ..
+                })
+            ),
+            "number of entries does not match"
+        );
+
+        file.write("%%MatrixMarket matrix array real general\n2 2\n1\n0\n0\n1\n");
+        assert!(
+            matches!(
+                Graph::try_from_matrix_market(path_str),
+                Err(GraphError::BadHeader { line: 1, .. })
+            ),
+            "array format is not supported"
+        );
+    }
+}
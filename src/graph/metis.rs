@@ -0,0 +1,261 @@
+use crate::graph::error::{bad_header, columns, parse_token, parse_vertex};
+use crate::graph::{BuildPolicy, Directedness, EdgeData, Graph, GraphBuilder, GraphError};
+
+/// # METIS
+///
+/// Reader for the adjacency list format of the METIS graph partitioning tools.
+impl Graph {
+    /// ## from_metis
+    ///
+    /// Create an undirected Graph from the METIS file at `path_str`.
+    /// Lines prefixed by `%` are comments. The header `n m [fmt [ncon]]` is followed by exactly
+    /// `n` lines, where line `i` lists the neighbors of vertex `i` (indices start at `1`), so an
+    /// empty line is an isolated vertex. Every edge is listed at both of its vertices.
+    ///
+    /// `fmt` has up to three digits: if the last one is `1`, every neighbor is followed by the
+    /// weight of the edge, which is kept in `edge_data`. If the middle one is `1`, every line
+    /// starts with `ncon` (default `1`) vertex weights. If the first one is `1`, every line
+    /// starts with the size of the vertex, in front of its weights. Vertex sizes and weights are
+    /// skipped.
+    /// Panics if the file is invalid, see `try_from_metis`.
+    pub fn from_metis(path_str: &str) -> Self {
+        Graph::try_from_metis(path_str).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_from_metis
+    ///
+    /// Create an undirected Graph from the METIS file at `path_str`, see `from_metis`
+    pub fn try_from_metis(path_str: &str) -> Result<Self, GraphError> {
+        Graph::try_from_metis_with_policy(path_str, BuildPolicy::default())
+    }
+
+    /// ## try_from_metis_with_policy
+    ///
+    /// Create an undirected Graph from the METIS file at `path_str`, where self loops and parallel
+    /// edges are handled according to `policy`, see `from_metis`
+    pub fn try_from_metis_with_policy(
+        path_str: &str,
+        policy: BuildPolicy,
+    ) -> Result<Self, GraphError> {
+        let file = std::fs::read_to_string(path_str)?;
+        let mut lines = file
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_i, line)| !line.starts_with('%'));
+
+        let (header_line, line) = lines
+            .next()
+            .ok_or_else(|| bad_header(1, "METIS header is missing"))?;
+        let header = columns(line, header_line, 2)?
+            .into_iter()
+            .map(|v| parse_token::<usize>(line, v, header_line))
+            .collect::<Result<Vec<_>, _>>()?;
+        if header.len() > 4 {
+            return Err(bad_header(
+                header_line,
+                "METIS header must be n m [fmt [ncon]]",
+            ));
+        }
+        let (n, m) = (header[0], header[1]);
+        let fmt = header.get(2).copied().unwrap_or(0);
+        if fmt % 10 > 1 || fmt / 10 % 10 > 1 || fmt / 100 > 1 {
+            return Err(bad_header(
+                header_line,
+                "METIS fmt must consist of the digits 0 and 1",
+            ));
+        }
+        let has_edge_weights = fmt % 10 == 1;
+        // the vertex size comes before the vertex weights
+        let has_vert_size = fmt / 100 == 1;
+        let num_vert_weights = if fmt / 10 % 10 == 1 {
+            header.get(3).copied().unwrap_or(1)
+        } else {
+            0
+        };
+        let num_vert_columns = has_vert_size as usize + num_vert_weights;
+
+        let mut builder = GraphBuilder::new(n)
+            .with_directedness(Directedness::Undirected)
+            .with_policy(policy);
+        let mut edge_data = Vec::new();
+        let mut num_entries = 0;
+        let mut num_lines = 0;
+
+        for (v, (i, line)) in lines.enumerate() {
+            // trailing empty lines at the end of the file are no vertices
+            if v >= n {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(GraphError::SizeMismatch {
+                    line: i,
+                    message: String::from("METIS file has more than n vertices"),
+                    expected: n,
+                    found: v + 1,
+                });
+            }
+            num_lines += 1;
+
+            let values: Vec<_> = line.split_whitespace().skip(num_vert_columns).collect();
+            if has_edge_weights && values.len() % 2 == 1 {
+                return Err(GraphError::Truncated {
+                    line: i,
+                    byte: line.trim_end().len(),
+                });
+            }
+
+            let step = if has_edge_weights { 2 } else { 1 };
+            for entry in values.chunks(step) {
+                let u = parse_vertex(line, entry[0], i, n)?;
+                num_entries += 1;
+                // every edge is listed at both of its vertices
+                if v <= u {
+                    builder.add_edge(v, u);
+                }
+
+                if has_edge_weights && v <= u {
+                    edge_data.push(EdgeData {
+                        from: v,
+                        to: u,
+                        weight: parse_token(line, entry[1], i)?,
+                        timestamp: None,
+                    });
+                }
+            }
+        }
+
+        if num_lines != n {
+            return Err(GraphError::SizeMismatch {
+                line: header_line,
+                message: String::from("METIS file has less than n vertices"),
+                expected: n,
+                found: num_lines,
+            });
+        }
+        if num_entries != 2 * m {
+            return Err(GraphError::SizeMismatch {
+                line: header_line,
+                message: String::from("number of METIS edges does not match the header"),
+                expected: m,
+                found: num_entries / 2,
+            });
+        }
+
+        let mut g = builder.try_build()?;
+        g.edge_data = edge_data;
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::{from_content, neighbors, TempFile};
+    use crate::graph::{Directedness, Graph, GraphError};
+
+    #[test]
+    fn tutte_graph() {
+        let g = Graph::from_metis("src/data/tutte_graph.metis");
+        let expect = Graph::from_graph6("src/data/tutte_graph.g6");
+        assert_eq!(g.directedness(), Directedness::Undirected);
+        assert_eq!(neighbors(&g), neighbors(&expect), "tutte graph from METIS");
+    }
+
+    #[test]
+    fn weights() {
+        // path 0 - 1 - 2 with vertex weights, edge weights and an isolated vertex 3
+        let g = from_content(
+            "metis_weights",
+            "4 2 011\n5 2 7\n1 1 7 3 9\n2 2 9\n4\n",
+            Graph::from_metis,
+        );
+        assert_eq!(
+            neighbors(&g),
+            vec![vec![1], vec![0, 2], vec![1], vec![]],
+            "vertex weights are skipped"
+        );
+        let weights: Vec<_> = g.edge_data.iter().map(|e| e.weight).collect();
+        assert_eq!(weights, vec![7., 9.], "edge weights are kept once");
+    }
+
+    #[test]
+    fn vertex_sizes() {
+        // path 0 - 1 - 2, every line starts with the size of the vertex
+        let g = from_content(
+            "metis_sizes",
+            "3 2 100
+3 2
+1 1 3
+2 2
+",
+            Graph::from_metis,
+        );
+        assert_eq!(
+            neighbors(&g),
+            vec![vec![1], vec![0, 2], vec![1]],
+            "vertex sizes are skipped"
+        );
+
+        let g = from_content(
+            "metis_sizes_weights",
+            "3 2 111 2
+3 0 0 2 7
+1 1 1 1 7 3 9
+2 2 2 2 9
+",
+            Graph::from_metis,
+        );
+        assert_eq!(
+            neighbors(&g),
+            vec![vec![1], vec![0, 2], vec![1]],
+            "vertex sizes and weights are skipped"
+        );
+        let weights: Vec<_> = g.edge_data.iter().map(|e| e.weight).collect();
+        assert_eq!(
+            weights,
+            vec![7., 9.],
+            "edge weights after the vertex columns"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "number of METIS edges does not match the header")]
+    fn wrong_edge_count() {
+        from_content("metis_count", "3 1\n2\n1 3\n2\n", Graph::from_metis);
+    }
+
+    #[test]
+    #[should_panic(expected = "METIS file has less than n vertices")]
+    fn missing_vertex() {
+        from_content("metis_vertices", "3 1\n2\n1\n", Graph::from_metis);
+    }
+
+    #[test]
+    fn errors() {
+        let file = TempFile::new("metis_err");
+        let path_str = file.path_str();
+
+        file.write("2 1 001\n2 5\n1\n");
+        assert!(
+            matches!(
+                Graph::try_from_metis(path_str),
+                Err(GraphError::Truncated { line: 3, byte: 1 })
+            ),
+            "edge weight is missing"
+        );
+
+        file.write("% comment\n2 1\n2\n1 3\n");
+        assert!(
+            matches!(
+                Graph::try_from_metis(path_str),
+                Err(GraphError::SizeMismatch {
+                    line: 4,
+                    expected: 2,
+                    found: 3,
+                    ..
+                })
+            ),
+            "neighbor is out of bounds"
+        );
+    }
+}
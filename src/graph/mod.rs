@@ -0,0 +1,1000 @@
+#[cfg(feature = "extensor_bitvec")]
+use crate::extensor::bitvec::ExTensor;
+#[cfg(feature = "extensor_dense_hashmap")]
+use crate::extensor::dense_hashmap::ExTensor;
+
+#[cfg(feature = "matrix_naive")]
+use crate::matrix::naive::Matrix;
+#[cfg(feature = "matrix_sparse_hash")]
+use crate::matrix::sparse_hash::Matrix;
+#[cfg(feature = "matrix_sparse_triples")]
+use crate::matrix::sparse_triples::Matrix;
+
+use crate::matrix::{blocked, parallel::Parallelism, symmetric};
+use std::collections::HashMap;
+use std::io;
+
+mod builder;
+mod components;
+mod dimacs;
+mod dot;
+mod edge_list;
+mod error;
+/// # generators
+///
+/// Constructors for well known families of undirected graphs and random graph models.
+/// The random models take a `seed`, so the same seed always produces the same Graph.
+/// None of the generated graphs has self loops or parallel edges.
+///
+/// For the structured families the number of `k`-paths is known in closed form
+/// (e.g. `complete_k_paths`). Like `algorithm::c`, a `k`-path is a sequence of `k` distinct
+/// vertices where consecutive vertices are adjacent, so every undirected path with at least
+/// two vertices is counted once per direction.
+pub mod generators;
+mod graph6;
+mod konect;
+mod matrix_market;
+mod metis;
+mod mutation;
+mod policy;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub use builder::GraphBuilder;
+pub use dot::DotOptions;
+pub use edge_list::EdgeListOptions;
+pub use error::GraphError;
+pub use konect::{EdgeData, KonectFormat};
+pub use policy::{BuildPolicy, DroppedEdges, EdgePolicy};
+
+use num_traits::Zero;
+use rand::{
+    distributions::{Bernoulli, Distribution, Uniform},
+    Rng,
+};
+
+/// # Directedness
+///
+/// Records whether the edges of a Graph have a direction.
+/// The adjacency matrix of an undirected Graph is always symmetric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Directedness {
+    Directed,
+    Undirected,
+}
+
+/// # AdjMat
+///
+/// The adjacency matrix of a Graph. Undirected graphs can be stored as a symmetric matrix,
+/// which only keeps the upper triangle (enabled via the `matrix_symmetric` feature).
+/// Graphs that do not fit into memory are stored as a blocked matrix in a file.
+#[derive(Debug, Clone, PartialEq)]
+enum AdjMat {
+    Full(Matrix<u8>),
+    Symmetric(symmetric::Matrix<u8>),
+    Blocked(blocked::Matrix),
+}
+
+impl AdjMat {
+    /// ## from_edges
+    ///
+    /// Create the adjacency matrix of a graph with `n` vertices from a list of `edges`.
+    /// For an undirected graph every edge is also added in the reverse direction.
+    fn from_edges(n: usize, directedness: Directedness, edges: Vec<(usize, usize)>) -> Self {
+        let triplets = edges.into_iter().map(|(i, j)| (i, j, 1)).collect();
+        AdjMat::from_triplets(n, directedness, triplets)
+    }
+
+    /// ## from_triplets
+    ///
+    /// Create the adjacency matrix of a graph with `n` vertices from the entries `(i, j, value)`,
+    /// see `from_edges`
+    fn from_triplets(
+        n: usize,
+        directedness: Directedness,
+        triplets: Vec<(usize, usize, u8)>,
+    ) -> Self {
+        match directedness {
+            Directedness::Directed => AdjMat::Full(Matrix::from_triplets(n, n, triplets)),
+            Directedness::Undirected if cfg!(feature = "matrix_symmetric") => {
+                AdjMat::Symmetric(symmetric::Matrix::from_triplets(n, n, triplets))
+            }
+            Directedness::Undirected => {
+                let both_directions = triplets
+                    .into_iter()
+                    .flat_map(|(i, j, v)| std::iter::once((i, j, v)).chain(Some((j, i, v))));
+                AdjMat::Full(Matrix::from_triplets(n, n, both_directions))
+            }
+        }
+    }
+
+    fn ncols(&self) -> usize {
+        match self {
+            AdjMat::Full(m) => m.ncols,
+            AdjMat::Symmetric(m) => m.ncols,
+            AdjMat::Blocked(m) => m.ncols,
+        }
+    }
+
+    fn neighbors_of(&self, i: usize) -> Box<dyn Iterator<Item = usize> + '_> {
+        self.try_neighbors_of(i)
+            .expect("could not read block of out-of-core matrix")
+    }
+
+    fn try_neighbors_of(&self, i: usize) -> io::Result<Box<dyn Iterator<Item = usize> + '_>> {
+        Ok(match self {
+            AdjMat::Full(m) => Box::new(m.neighbors_of(i)),
+            AdjMat::Symmetric(m) => Box::new(m.neighbors_of(i)),
+            AdjMat::Blocked(m) => Box::new(m.neighbors_of(i)?.into_iter()),
+        })
+    }
+
+    /// ## set_edge
+    ///
+    /// Add (`val = 1`) or remove (`val = 0`) the edge `(i, j)`.
+    /// For an undirected graph the reverse edge `(j, i)` is changed as well.
+    fn set_edge(&mut self, i: usize, j: usize, directedness: Directedness, val: u8) {
+        match self {
+            AdjMat::Full(m) => {
+                m.set(i, j, val);
+                if directedness == Directedness::Undirected {
+                    m.set(j, i, val);
+                }
+            }
+            AdjMat::Symmetric(m) => m.set(i, j, val),
+            AdjMat::Blocked(_m) => panic!("out-of-core matrix can not be changed"),
+        }
+    }
+
+    /// ## grow
+    ///
+    /// Add isolated vertices, so the graph has `n` vertices. The stored edges are not changed.
+    fn grow(&mut self, n: usize) {
+        match self {
+            AdjMat::Full(m) => m.grow(n, n),
+            AdjMat::Symmetric(m) => m.grow(n),
+            AdjMat::Blocked(_m) => panic!("out-of-core matrix can not be changed"),
+        }
+    }
+
+    /// ## relabel
+    ///
+    /// Create the adjacency matrix of a graph with `n` vertices, where vertex `i` becomes vertex
+    /// `f(i)`. The edges of all vertices with `f(i) = None` are removed.
+    fn relabel<F>(self, n: usize, f: F) -> Self
+    where
+        F: Fn(usize) -> Option<usize>,
+    {
+        let relabel_edge = |(i, j, val)| Some((f(i)?, f(j)?, val));
+        match self {
+            AdjMat::Full(m) => AdjMat::Full(Matrix::from_triplets(
+                n,
+                n,
+                m.into_triplets().filter_map(relabel_edge),
+            )),
+            AdjMat::Symmetric(m) => AdjMat::Symmetric(symmetric::Matrix::from_triplets(
+                n,
+                n,
+                m.into_triplets().filter_map(relabel_edge),
+            )),
+            AdjMat::Blocked(_m) => panic!("out-of-core matrix can not be changed"),
+        }
+    }
+}
+
+// only used by tests, since reading an out-of-core matrix can fail
+#[cfg(test)]
+impl PartialEq<Matrix<u8>> for AdjMat {
+    fn eq(&self, other: &Matrix<u8>) -> bool {
+        match self {
+            AdjMat::Full(m) => m == other,
+            AdjMat::Symmetric(m) => Matrix::new(m.nrows, m.ncols, m.to_dense()) == *other,
+            AdjMat::Blocked(m) => {
+                let triplets = m
+                    .triplets()
+                    .expect("could not read block of out-of-core matrix");
+                Matrix::from_triplets(m.nrows, m.ncols, triplets) == *other
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph {
+    adj_mat: Box<AdjMat>,
+    directedness: Directedness,
+    parallelism: Parallelism,
+    pub vert_data: Vec<usize>,
+    vert_labels: Vec<String>,
+    /// index of every label in `vert_labels`, see `index_of_label`
+    label_indices: HashMap<String, usize>,
+    pub edge_data: Vec<EdgeData>,
+    num_vert: usize,
+    dropped_edges: DroppedEdges,
+}
+
+/// # Graph
+///
+/// implementation of the Graph structure used
+/// currently a graph is just its adjacency matrix
+impl Graph {
+    /// ## from
+    ///
+    /// Construct a directed Graph with `n` vertices given an adjacency matrx `data`
+    pub fn from(n: usize, data: Vec<u8>) -> Self {
+        assert_eq!(data.len(), n * n, "data must correspond to a square matrix");
+        let adj_mat = Box::new(AdjMat::Full(Matrix::new(n, n, data)));
+
+        Graph {
+            adj_mat,
+            directedness: Directedness::Directed,
+            parallelism: Parallelism::default(),
+            vert_data: vec![],
+            vert_labels: Vec::new(),
+            label_indices: HashMap::new(),
+            edge_data: Vec::new(),
+            num_vert: n,
+            dropped_edges: DroppedEdges::default(),
+        }
+    }
+
+    /// ## from_edges
+    ///
+    /// Construct a directed Graph with `n` vertices from its `edges`, given as pairs `(from, to)`.
+    /// Only memory proportional to the number of edges is used, see `GraphBuilder`.
+    pub fn from_edges<I>(n: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        GraphBuilder::new(n).with_edges(edges).build()
+    }
+
+    /// # random_graph
+    ///
+    /// create a random graph with `n` vertices where each edge has probability `p`.
+    /// The graph is directed and can have self loops, see `generators::gnp` for a seeded
+    /// undirected random graph.
+    pub fn random_graph(n: usize, p: f64) -> Graph {
+        Graph::random_graph_with_policy(n, p, BuildPolicy::default())
+    }
+
+    /// # random_graph_with_policy
+    ///
+    /// create a random graph with `n` vertices where each edge has probability `p`, where the
+    /// self loops are handled according to `policy`, see `random_graph`.
+    /// Panics if a self loop is rejected.
+    pub fn random_graph_with_policy(n: usize, p: f64, policy: BuildPolicy) -> Graph {
+        assert!(0.0 <= p && p <= 1.0, "Probability must be in (0,1]");
+
+        let mut rng = rand::thread_rng();
+        let bernoulli = Bernoulli::new(p).unwrap();
+        let edges = (0..n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .filter(|_| bernoulli.sample(&mut rng));
+
+        GraphBuilder::new(n)
+            .with_policy(policy)
+            .with_edges(edges)
+            .build()
+    }
+
+    /// ## from_blocked
+    ///
+    /// Create a Graph whose adjacency matrix `adj_mat` is stored in a file (see `matrix::blocked`).
+    /// During `compute_walk_sum` the blocks are streamed from the file, so only the current and
+    /// the next vector of the walk sum are kept in memory.
+    /// Since the matrix is not read, it is up to the caller to specify its `directedness`.
+    pub fn from_blocked(adj_mat: blocked::Matrix, directedness: Directedness) -> Self {
+        let num_vert = adj_mat.ncols;
+
+        Graph {
+            adj_mat: Box::new(AdjMat::Blocked(adj_mat)),
+            directedness,
+            parallelism: Parallelism::default(),
+            vert_data: Vec::with_capacity(num_vert),
+            vert_labels: Vec::new(),
+            label_indices: HashMap::new(),
+            edge_data: Vec::new(),
+            num_vert,
+            dropped_edges: DroppedEdges::default(),
+        }
+    }
+
+    /// ## compute_walk_sum
+    ///
+    /// Given an usize `k` and an extensor mapping compute its walk sum.
+    /// The mapping is a tuple of closures, where the first element is a function from
+    /// a vertex to a extensor and the second element is a function from two vertices (an edge) to
+    /// an f64. The walk sum is calculated as
+    ///
+    /// f(G, ξ) = (1 1 .. 1) A^(k-1) (ξ(v_1) ξ(v_2) ... ξ(v_n))^T
+    ///
+    /// Panics if a block of an out-of-core matrix can not be read, see `try_compute_walk_sum`.
+    pub fn compute_walk_sum(&self, k: usize, coding: Vec<ExTensor>) -> ExTensor {
+        self.try_compute_walk_sum(k, coding)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_compute_walk_sum
+    ///
+    /// Compute the walk sum like `compute_walk_sum`, but return an error if a block of an
+    /// out-of-core matrix can not be read
+    pub fn try_compute_walk_sum(
+        &self,
+        k: usize,
+        coding: Vec<ExTensor>,
+    ) -> Result<ExTensor, GraphError> {
+        // add extensor coding to vertices and transform back to a matrix
+        let parallelism = &self.parallelism;
+        let res = match &*self.adj_mat {
+            AdjMat::Full(m) => {
+                let a = m.add_coding(&coding);
+                Graph::walk_sum(|x| Ok(a.par_mul(x, parallelism)), m.ncols, k, &coding)
+            }
+            AdjMat::Symmetric(m) => {
+                let a = m.add_coding(&coding);
+                Graph::walk_sum(|x| Ok(a.par_mul(x, parallelism)), m.ncols, k, &coding)
+            }
+            AdjMat::Blocked(m) => Graph::walk_sum(
+                |x| m.mul_coded(&coding, x, parallelism),
+                m.ncols,
+                k,
+                &coding,
+            ),
+        };
+
+        Ok(res?)
+    }
+
+    /// ## compute_cycle_sum
+    ///
+    /// Given an usize `k` and an extensor mapping compute the sum over all closed walks with `k`
+    /// vertices, i.e. the walks `v_1, ..., v_k` with an edge from `v_k` back to `v_1`.
+    /// Like in `compute_walk_sum`, the walks that visit a vertex twice vanish, so only the
+    /// `k`-cycles remain. Every cycle is summed once for each of its `k` start vertices.
+    ///
+    /// f(G, ξ) = sum over all vertices s of (A^(k-1) b_s)_s, where (b_s)_j = ξ(v_j) if there
+    /// is an edge from v_j to s and 0 otherwise
+    ///
+    /// Every start vertex needs its own `k - 1` matrix vector multiplications, so this costs
+    /// `n * (k - 1)` of them, `n` times as many as `compute_walk_sum`. The in-neighbors of all
+    /// vertices are collected first, which reads the whole adjacency matrix once.
+    ///
+    /// Panics if a block of an out-of-core matrix can not be read, see `try_compute_cycle_sum`.
+    pub fn compute_cycle_sum(&self, k: usize, coding: Vec<ExTensor>) -> ExTensor {
+        self.try_compute_cycle_sum(k, coding)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// ## try_compute_cycle_sum
+    ///
+    /// Compute the closed walk sum like `compute_cycle_sum`, but return an error if a block of
+    /// an out-of-core matrix can not be read
+    pub fn try_compute_cycle_sum(
+        &self,
+        k: usize,
+        coding: Vec<ExTensor>,
+    ) -> Result<ExTensor, GraphError> {
+        let in_neighbors = self.try_in_neighbors()?;
+        self.try_cycle_sum_with(k, coding, &in_neighbors)
+    }
+
+    /// ## try_in_neighbors
+    ///
+    /// the vertices with an edge to `v` for every vertex `v`, sorted
+    pub(crate) fn try_in_neighbors(&self) -> Result<Vec<Vec<usize>>, GraphError> {
+        let mut in_neighbors = vec![Vec::new(); self.num_vert];
+        for i in 0..self.num_vert {
+            for j in self.adj_mat.try_neighbors_of(i)? {
+                in_neighbors[j].push(i);
+            }
+        }
+
+        Ok(in_neighbors)
+    }
+
+    /// ## try_cycle_sum_with
+    ///
+    /// the closed walk sum of `try_compute_cycle_sum` with the already collected
+    /// `in_neighbors` (see `try_in_neighbors`), so they can be reused for many codings
+    pub(crate) fn try_cycle_sum_with(
+        &self,
+        k: usize,
+        coding: Vec<ExTensor>,
+        in_neighbors: &[Vec<usize>],
+    ) -> Result<ExTensor, GraphError> {
+        assert!(k >= 2, "a cycle needs at least two vertices");
+
+        // add extensor coding to vertices and transform back to a matrix
+        let parallelism = &self.parallelism;
+        let res = match &*self.adj_mat {
+            AdjMat::Full(m) => {
+                let a = m.add_coding(&coding);
+                Graph::cycle_sum(|x| Ok(a.par_mul(x, parallelism)), k, &coding, in_neighbors)
+            }
+            AdjMat::Symmetric(m) => {
+                let a = m.add_coding(&coding);
+                Graph::cycle_sum(|x| Ok(a.par_mul(x, parallelism)), k, &coding, in_neighbors)
+            }
+            AdjMat::Blocked(m) => Graph::cycle_sum(
+                |x| m.mul_coded(&coding, x, parallelism),
+                k,
+                &coding,
+                in_neighbors,
+            ),
+        };
+
+        Ok(res?)
+    }
+
+    /// ## cycle_sum
+    ///
+    /// compute the closed walk sum of `compute_cycle_sum`, where `mul` multiplies the already
+    /// coded adjacency matrix `A` with a vector
+    fn cycle_sum<F>(
+        mul: F,
+        k: usize,
+        coding: &[ExTensor],
+        in_neighbors: &[Vec<usize>],
+    ) -> io::Result<ExTensor>
+    where
+        F: Fn(&[ExTensor]) -> io::Result<Vec<ExTensor>>,
+    {
+        let n = in_neighbors.len();
+        let mut sum = ExTensor::zero();
+        for (s, in_neighbors_s) in in_neighbors.iter().enumerate() {
+            let mut res = vec![ExTensor::zero(); n];
+            for j in in_neighbors_s.iter() {
+                res[*j] = coding[*j].clone();
+            }
+            for _ in 0..(k - 1) {
+                res = mul(&res)?;
+            }
+            sum = sum + res.swap_remove(s);
+        }
+
+        Ok(sum)
+    }
+
+    /// ## walk_sum
+    ///
+    /// compute `(1 1 .. 1) A^(k-1) (coding[0] coding[1] ... coding[ncols-1])^T` where `mul`
+    /// multiplies the already coded adjacency matrix `A` with a vector
+    fn walk_sum<F>(mul: F, ncols: usize, k: usize, coding: &[ExTensor]) -> io::Result<ExTensor>
+    where
+        F: Fn(&[ExTensor]) -> io::Result<Vec<ExTensor>>,
+    {
+        let b = (0..ncols).map(|i| coding[i].clone()).collect::<Vec<_>>();
+
+        let mut res = mul(&b)?;
+        for _ in 1..(k - 1) {
+            res = mul(&res)?;
+        }
+
+        Ok(res.into_iter().fold(ExTensor::zero(), |acc, v| acc + v))
+    }
+
+    /// ## set_parallelism
+    ///
+    /// Set the threads that are used for the matrix vector multiplications in
+    /// `compute_walk_sum` and `compute_cycle_sum`. By default everything is computed serially,
+    /// e.g. `Parallelism::available()` uses all available cpus for large graphs.
+    pub fn set_parallelism(&mut self, parallelism: Parallelism) {
+        self.parallelism = parallelism;
+    }
+
+    /// ## num_vert
+    ///
+    /// Returns the number of vertices, see `add_vertex` and `remove_vertex` to change it.
+    pub fn num_vert(&self) -> usize {
+        self.num_vert
+    }
+
+    /// ## directedness
+    ///
+    /// Returns whether the edges of the Graph are directed or undirected.
+    pub fn directedness(&self) -> Directedness {
+        self.directedness
+    }
+
+    /// ## color_coding
+    ///
+    /// add a color (number in 1..=k) to every vertex
+    /// the colors are stored in the `vert_data` field
+    pub(crate) fn color_coding(&self, k: usize) -> Self {
+        let num_vert = self.adj_mat.ncols();
+        let rng = rand::thread_rng();
+        let colors: Vec<_> = rng
+            .sample_iter(&Uniform::new(1, k + 1))
+            .take(num_vert)
+            .collect();
+
+        Graph {
+            adj_mat: self.adj_mat.clone(),
+            directedness: self.directedness,
+            parallelism: self.parallelism,
+            vert_data: colors,
+            vert_labels: self.vert_labels.clone(),
+            label_indices: self.label_indices.clone(),
+            edge_data: self.edge_data.clone(),
+            num_vert,
+            dropped_edges: self.dropped_edges,
+        }
+    }
+
+    /// ## dropped_edges
+    ///
+    /// Returns the number of self loops and parallel edges that were removed when the Graph was
+    /// built, see `BuildPolicy`
+    pub fn dropped_edges(&self) -> DroppedEdges {
+        self.dropped_edges
+    }
+
+    /// ## neighbors_of
+    ///
+    /// Iterate over the indices of all vertices that are reachable from vertex `i` via one edge.
+    /// For the sparse matrix implementations this takes time proportional to the degree of `i`.
+    /// The dense `matrix_naive` backend is exempt and scans the whole row, so it takes time
+    /// proportional to the number of vertices.
+    /// Panics if a block of an out-of-core matrix can not be read, see `try_neighbors_of`.
+    pub fn neighbors_of(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj_mat.neighbors_of(i)
+    }
+
+    /// ## try_neighbors_of
+    ///
+    /// Iterate over the neighbors of vertex `i` like `neighbors_of`, but return an error if the
+    /// block of an out-of-core matrix can not be read
+    pub fn try_neighbors_of(
+        &self,
+        i: usize,
+    ) -> Result<impl Iterator<Item = usize> + '_, GraphError> {
+        Ok(self.adj_mat.try_neighbors_of(i)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "extensor_bitvec")]
+    use crate::extensor::bitvec::ExTensor;
+    #[cfg(feature = "extensor_dense_hashmap")]
+    use crate::extensor::dense_hashmap::ExTensor;
+    #[cfg(feature = "matrix_naive")]
+    use crate::matrix::naive::Matrix;
+    #[cfg(feature = "matrix_sparse_hash")]
+    use crate::matrix::sparse_hash::Matrix;
+    #[cfg(feature = "matrix_sparse_triples")]
+    use crate::matrix::sparse_triples::Matrix;
+
+    use crate::graph::test_utils::TempFile;
+    use crate::graph::{Directedness, Graph, GraphBuilder, GraphError};
+    use crate::matrix::{blocked, parallel::Parallelism};
+    use crate::utils;
+    use num_traits::Zero;
+
+    #[test]
+    #[should_panic(expected = "could not read file")]
+    fn test_graph6_not_found() {
+        Graph::from_graph6("src/data/this_is_not_a_file.g6");
+    }
+
+    /// returns the adjacency matrix of the n path graph
+    fn get_n_path_graph_adj_mat(n: usize) -> Matrix<u8> {
+        let mut res = Vec::with_capacity(n * n);
+        res.reserve(n * n);
+
+        for i in 0..n {
+            for j in 0..n {
+                if i == j + 1 || i + 1 == j {
+                    res.push(1);
+                } else {
+                    res.push(0);
+                }
+            }
+        }
+
+        Matrix::new(n, n, res)
+    }
+
+    #[test]
+    fn graph6_header() {
+        let g = Graph::from_graph6("src/data/path10_with_header.g6");
+        let expect = get_n_path_graph_adj_mat(10);
+        assert_eq!(
+            *g.adj_mat, expect,
+            "Graph6 file with header should be read correctly"
+        );
+    }
+
+    #[test]
+    fn tutte_graph() {
+        let g = Graph::from_graph6("src/data/tutte_graph.g6");
+
+        let tutte_mat_file = std::fs::read_to_string("src/data/tutte_mat.txt")
+            .expect("could not read tutte_mat.txt");
+        let tutte_mat_file = tutte_mat_file.replace('\n', " ");
+
+        let tutte_mat = tutte_mat_file
+            .trim()
+            .split(" ")
+            .map(|c| c.parse::<u8>().unwrap())
+            .collect::<Vec<u8>>();
+
+        let t_mat = Matrix::new(46, 46, tutte_mat);
+        assert_eq!(*g.adj_mat, t_mat, "Tutte Graph should be read correctly");
+    }
+
+    #[test]
+    fn adj_mat() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        let expect = get_n_path_graph_adj_mat(10);
+        assert_eq!(*g.adj_mat, expect, "10 path graph should be read correctly");
+    }
+
+    #[test]
+    fn big_graph() {
+        let g = Graph::from_graph6("src/data/path100.g6");
+        let expect = get_n_path_graph_adj_mat(100);
+        assert_eq!(
+            *g.adj_mat, expect,
+            "100 path graph should be read correctly"
+        );
+    }
+
+    #[test]
+    fn big_graph_with_header() {
+        let g = Graph::from_graph6("src/data/path100_with_header.g6");
+        let expect = get_n_path_graph_adj_mat(100);
+        assert_eq!(
+            *g.adj_mat, expect,
+            "100 path graph with header should be read correctly"
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        let first: Vec<_> = g.neighbors_of(0).collect();
+        let inner: Vec<_> = g.neighbors_of(4).collect();
+        let last: Vec<_> = g.neighbors_of(9).collect();
+        assert_eq!(first, vec![1], "first vertex of a path has one neighbor");
+        assert_eq!(
+            inner,
+            vec![3, 5],
+            "inner vertex of a path has two neighbors"
+        );
+        assert_eq!(last, vec![8], "last vertex of a path has one neighbor");
+    }
+
+    #[test]
+    fn from_edges() {
+        let g = Graph::from_edges(3, vec![(0, 1), (1, 2), (2, 0)]);
+        let expect = Matrix::new(3, 3, vec![0, 1, 0, 0, 0, 1, 1, 0, 0]);
+        assert_eq!(*g.adj_mat, expect, "graph from edges");
+    }
+
+    #[test]
+    fn directedness() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        assert_eq!(
+            g.directedness(),
+            Directedness::Undirected,
+            "graph6 is undirected"
+        );
+        let g = Graph::from_tsv("src/data/out.brunson_southern-women_southern-women");
+        assert_eq!(
+            g.directedness(),
+            Directedness::Undirected,
+            "bipartite tsv is undirected"
+        );
+        let g = Graph::from_tsv("src/data/out.moreno_innovation_innovation");
+        assert_eq!(
+            g.directedness(),
+            Directedness::Directed,
+            "asym tsv is directed"
+        );
+    }
+
+    /// `g` must be the bipartite graph with biadjacency matrix `expect`
+    fn assert_bipartite(g: &Graph, expect: &Matrix<u8>) {
+        let (n1, n2) = (expect.nrows, expect.ncols);
+        let values = expect.to_dense();
+        let edges = (0..n1)
+            .flat_map(|i| (0..n2).map(move |j| (i, j)))
+            .filter(|(i, j)| values[i * n2 + j] != 0)
+            .map(|(i, j)| (i, n1 + j));
+        let expect = GraphBuilder::new(n1 + n2)
+            .with_directedness(Directedness::Undirected)
+            .with_edges(edges)
+            .build();
+
+        assert_eq!(
+            g.num_vert(),
+            n1 + n2,
+            "bipartite graph has n1 + n2 vertices"
+        );
+        for i in 0..g.num_vert() {
+            assert_eq!(
+                g.neighbors_of(i).collect::<Vec<_>>(),
+                expect.neighbors_of(i).collect::<Vec<_>>(),
+                "tsv reads correct bipartite adj_mat"
+            );
+        }
+    }
+
+    #[test]
+    fn read_tsv() {
+        let g = Graph::from_tsv("src/data/out.brunson_southern-women_southern-women");
+        let expect = Matrix::new(
+            5,
+            5,
+            vec![
+                1, 1, 1, 0, 0, 0, 1, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0, 1, 0, 0, 0, 1, 1, 1,
+            ],
+        );
+        assert_bipartite(&g, &expect);
+    }
+
+    #[test]
+    fn read_tsv_2() {
+        let g = Graph::from_tsv("src/data/out.brunson_revolution_revolution");
+        let expect = Matrix::new(
+            136,
+            5,
+            vec![
+                1, 1, 0, 0, 0, // 1
+                1, 1, 1, 0, 0, // 2
+                1, 0, 0, 0, 0, // 3
+                1, 0, 1, 0, 0, // 4
+                0, 0, 0, 1, 0, // 5
+                0, 0, 0, 0, 1, // 6
+                1, 0, 0, 0, 0, // 7
+                1, 0, 1, 0, 0, // 8
+                0, 0, 0, 1, 0, // 9
+                1, 0, 0, 0, 1, // 10
+                0, 0, 0, 1, 0, // 11
+                0, 0, 0, 1, 0, // 12
+                1, 0, 0, 0, 0, // 13
+                0, 0, 1, 0, 0, // 14
+                0, 0, 1, 0, 0, // 15
+                0, 0, 0, 1, 0, // 16
+                1, 0, 0, 0, 0, // 17
+                0, 0, 0, 1, 0, // 18
+                0, 0, 0, 1, 0, // 19
+                0, 0, 0, 1, 0, // 20
+                1, 0, 0, 0, 0, // 21
+                0, 0, 0, 1, 0, // 22
+                0, 0, 0, 1, 0, // 23
+                1, 0, 0, 0, 0, // 24
+                1, 0, 0, 0, 0, // 25
+                1, 0, 0, 0, 0, // 26
+                1, 0, 0, 0, 1, // 27
+                1, 0, 0, 0, 0, // 28
+                0, 0, 0, 1, 0, // 29
+                1, 0, 0, 0, 0, // 30
+                1, 1, 1, 0, 0, // 31
+                0, 0, 0, 0, 1, // 32
+                0, 0, 0, 1, 0, // 33
+                1, 0, 0, 0, 0, // 34
+                1, 0, 0, 0, 0, // 35
+                0, 1, 0, 0, 0, // 36
+                0, 1, 0, 0, 0, // 37
+                0, 0, 0, 1, 1, // 38
+                0, 0, 1, 0, 0, // 39
+                0, 1, 0, 0, 0, // 40
+                1, 0, 1, 0, 0, // 41
+                0, 0, 0, 1, 0, // 42
+                0, 1, 0, 0, 0, // 43
+                0, 0, 0, 1, 0, // 44
+                1, 0, 0, 0, 1, // 45
+                1, 0, 0, 0, 0, // 46
+                0, 0, 0, 1, 0, // 47
+                0, 0, 0, 0, 1, // 48
+                0, 0, 0, 1, 0, // 49
+                0, 1, 0, 0, 0, // 50
+                0, 0, 0, 1, 0, // 51
+                0, 0, 0, 1, 0, // 52
+                1, 0, 0, 0, 0, // 53
+                0, 0, 0, 1, 0, // 54
+                1, 0, 1, 0, 0, // 55
+                0, 0, 1, 0, 0, // 56
+                0, 0, 0, 1, 0, // 57
+                0, 1, 0, 0, 0, // 58
+                1, 0, 0, 0, 0, // 59
+                0, 0, 1, 0, 0, // 60
+                0, 0, 0, 1, 0, // 61
+                1, 0, 0, 0, 0, // 62
+                0, 0, 0, 1, 0, // 63
+                1, 0, 0, 0, 0, // 64
+                1, 0, 0, 0, 0, // 65
+                0, 0, 0, 1, 0, // 66
+                0, 0, 0, 1, 0, // 67
+                0, 0, 0, 1, 0, // 68
+                0, 0, 0, 1, 0, // 69
+                1, 0, 0, 0, 0, // 70
+                1, 0, 0, 0, 0, // 71
+                0, 0, 0, 1, 0, // 72
+                1, 0, 0, 0, 0, // 73
+                0, 1, 0, 0, 0, // 74
+                0, 0, 0, 1, 0, // 75
+                1, 0, 0, 0, 0, // 76
+                0, 0, 1, 0, 0, // 77
+                0, 0, 0, 1, 0, // 78
+                0, 0, 0, 1, 0, // 79
+                1, 0, 0, 0, 0, // 80
+                0, 0, 0, 1, 0, // 81
+                1, 0, 0, 0, 0, // 82
+                0, 0, 0, 1, 0, // 83
+                1, 0, 1, 0, 0, // 84
+                0, 0, 0, 1, 0, // 85
+                1, 0, 0, 0, 0, // 86
+                0, 0, 0, 1, 0, // 87
+                0, 0, 0, 1, 0, // 88
+                0, 1, 1, 0, 0, // 89
+                0, 0, 0, 1, 0, // 90
+                1, 0, 0, 0, 0, // 91
+                1, 0, 0, 0, 0, // 92
+                1, 0, 0, 0, 0, // 93
+                1, 0, 0, 0, 0, // 94
+                0, 0, 0, 1, 0, // 95
+                1, 0, 0, 0, 0, // 96
+                0, 0, 0, 1, 0, // 97
+                0, 1, 0, 0, 0, // 98
+                0, 0, 1, 0, 0, // 99
+                0, 0, 0, 1, 0, // 100
+                0, 0, 1, 0, 0, // 101
+                1, 0, 0, 0, 0, // 102
+                1, 0, 0, 0, 0, // 103
+                0, 0, 0, 1, 0, // 104
+                0, 1, 1, 0, 0, // 105
+                1, 1, 0, 1, 0, // 106
+                1, 0, 0, 0, 0, // 107
+                0, 0, 0, 1, 0, // 108
+                1, 0, 0, 0, 0, // 109
+                1, 0, 0, 0, 0, // 110
+                0, 0, 0, 1, 0, // 111
+                0, 0, 0, 0, 1, // 112
+                0, 0, 0, 1, 0, // 113
+                1, 0, 0, 0, 0, // 114
+                1, 0, 0, 0, 0, // 115
+                1, 0, 0, 0, 0, // 116
+                1, 0, 0, 0, 0, // 117
+                0, 0, 1, 0, 0, // 118
+                1, 0, 0, 0, 0, // 119
+                1, 0, 0, 0, 0, // 120
+                0, 0, 0, 1, 0, // 121
+                1, 0, 0, 0, 0, // 122
+                0, 0, 0, 0, 1, // 123
+                0, 1, 0, 0, 0, // 124
+                1, 0, 0, 1, 0, // 125
+                1, 1, 1, 1, 0, // 126
+                0, 0, 0, 1, 0, // 127
+                0, 0, 0, 1, 0, // 128
+                0, 0, 0, 1, 1, // 129
+                0, 0, 1, 0, 0, // 130
+                1, 0, 0, 0, 0, // 131
+                0, 0, 0, 1, 0, // 132
+                0, 0, 0, 1, 0, // 133
+                0, 1, 0, 0, 0, // 134
+                1, 0, 0, 0, 0, // 135
+                1, 0, 1, 0, 0, // 136
+            ],
+        );
+        assert_bipartite(&g, &expect);
+    }
+
+    #[test]
+    fn compute_walk() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        let k = 3;
+        let res = g.compute_walk_sum(k, utils::create_vandermonde(g.num_vert(), k));
+        let zero = ExTensor::zero();
+        assert_ne!(
+            res, zero,
+            "compute walk with vandermonde coding should not be zero"
+        );
+    }
+
+    #[test]
+    fn compute_walk_2() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        let k = 5;
+        let res = g.compute_walk_sum(k, utils::create_vandermonde(g.num_vert(), k));
+        let zero = ExTensor::zero();
+        assert_ne!(
+            res, zero,
+            "compute walk with vandermonde coding should not be zero"
+        );
+    }
+
+    #[test]
+    fn compute_walk_parallel() {
+        let mut g = Graph::from_graph6("src/data/K10.g6");
+        let k = 4;
+        let coding = utils::create_vandermonde(g.num_vert(), k);
+
+        g.set_parallelism(Parallelism::serial());
+        let serial = g.compute_walk_sum(k, coding.clone());
+        for num_threads in 2..=4 {
+            g.set_parallelism(Parallelism::new(num_threads).with_min_rows_per_thread(1));
+            let res = g.compute_walk_sum(k, coding.clone());
+            assert_eq!(
+                res, serial,
+                "result does not depend on the number of threads"
+            );
+        }
+    }
+
+    #[test]
+    fn compute_walk_blocked() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        let file = TempFile::new("graph_blocked");
+        let path = file.path();
+        let entries: Vec<_> = (0..g.num_vert())
+            .flat_map(|i| g.neighbors_of(i).map(move |j| (i, j)))
+            .collect();
+        let blocked =
+            blocked::Matrix::create(path, g.num_vert(), g.num_vert(), 3, entries).unwrap();
+        let b = Graph::from_blocked(blocked, Directedness::Undirected);
+        for i in 0..g.num_vert() {
+            let expect: Vec<_> = g.neighbors_of(i).collect();
+            let res: Vec<_> = b.neighbors_of(i).collect();
+            assert_eq!(res, expect, "blocked graph has the same edges");
+        }
+
+        let k = 4;
+        let coding = utils::create_vandermonde(g.num_vert(), k);
+        let expect = g.compute_walk_sum(k, coding.clone()).coeffs();
+        let res = b.compute_walk_sum(k, coding).coeffs();
+        assert_eq!(res, expect, "blocked graph has the same walk sum");
+    }
+
+    #[test]
+    fn blocked_read_error() {
+        let g = Graph::from_graph6("src/data/path10.g6");
+        let file = TempFile::new("graph_blocked_error");
+        let path = file.path();
+        let entries: Vec<_> = (0..g.num_vert())
+            .flat_map(|i| g.neighbors_of(i).map(move |j| (i, j)))
+            .collect();
+        let blocked =
+            blocked::Matrix::create(path, g.num_vert(), g.num_vert(), 3, entries).unwrap();
+        let b = Graph::from_blocked(blocked, Directedness::Undirected);
+
+        // the entries are gone, but the header is still in memory
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_len(0)
+            .unwrap();
+        let k = 3;
+        let coding = utils::create_vandermonde(g.num_vert(), k);
+        assert!(
+            matches!(b.try_neighbors_of(4), Err(GraphError::Io(_))),
+            "neighbors can not be read"
+        );
+        assert!(
+            matches!(
+                b.try_compute_walk_sum(k, coding.clone()),
+                Err(GraphError::Io(_))
+            ),
+            "walk sum can not be read"
+        );
+        assert!(
+            matches!(b.try_compute_cycle_sum(k, coding), Err(GraphError::Io(_))),
+            "cycle sum can not be read"
+        );
+    }
+
+    #[test]
+    fn compute_walk_3() {
+        let g = Graph::from_graph6("src/data/path3.g6");
+        let k = 5;
+        let res = g.compute_walk_sum(k, utils::create_vandermonde(g.num_vert(), k));
+        assert_eq!(
+            res.is_zero(),
+            true,
+            "compute walk with vandermonde coding should be zero"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::graph::{AdjMat, Directedness, Graph};
+
+/// # Mutation
+///
+/// Add and remove vertices and edges of a Graph. All matrix implementations can be changed,
+/// except for the out-of-core `blocked` matrix. `num_vert`, `vert_data`, `vert_labels` and
+/// `edge_data` are kept consistent with the adjacency matrix.
+impl Graph {
+    /// ## add_edge
+    ///
+    /// Add the edge `(from, to)`. For an undirected Graph this also adds `(to, from)`.
+    /// Adding an edge that already exists does not change the Graph.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.assert_vertex(from);
+        self.assert_vertex(to);
+        self.adj_mat.set_edge(from, to, self.directedness, 1);
+    }
+
+    /// ## remove_edge
+    ///
+    /// Remove the edge `(from, to)` together with its `edge_data`.
+    /// For an undirected Graph this also removes `(to, from)`.
+    /// Returns whether the edge existed.
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        if !self.has_edge(from, to) {
+            return false;
+        }
+        self.adj_mat.set_edge(from, to, self.directedness, 0);
+
+        let directedness = self.directedness;
+        self.edge_data.retain(|e| {
+            let same = e.from == from && e.to == to;
+            let reverse = e.from == to && e.to == from;
+            !(same || directedness == Directedness::Undirected && reverse)
+        });
+        true
+    }
+
+    /// ## has_edge
+    ///
+    /// Returns whether the edge `(from, to)` exists
+    pub fn has_edge(&self, from: usize, to: usize) -> bool {
+        self.assert_vertex(from);
+        self.assert_vertex(to);
+        self.neighbors_of(from).any(|v| v == to)
+    }
+
+    /// ## degree
+    ///
+    /// Returns the number of neighbors of vertex `v`, for a directed Graph its out-degree.
+    /// A self loop counts as one neighbor.
+    pub fn degree(&self, v: usize) -> usize {
+        self.assert_vertex(v);
+        self.neighbors_of(v).count()
+    }
+
+    /// ## num_edges
+    ///
+    /// Returns the number of edges, an undirected edge is counted once
+    pub fn num_edges(&self) -> usize {
+        self.edges().count()
+    }
+
+    /// ## edges
+    ///
+    /// Iterate over all edges as pairs `(from, to)`, sorted by `from`.
+    /// For an undirected Graph every edge is returned once with `from <= to`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let undirected = self.directedness == Directedness::Undirected;
+        (0..self.num_vert).flat_map(move |i| {
+            self.neighbors_of(i)
+                .filter(move |j| !undirected || i <= *j)
+                .map(move |j| (i, j))
+        })
+    }
+
+    /// ## add_vertex
+    ///
+    /// Add an isolated vertex and return its index, which is the old `num_vert`.
+    /// If the vertices have data or labels, the new vertex gets the data `0` and its index as
+    /// label. If another vertex already has this label, `_1`, `_2`, ... is appended until the
+    /// label is unique.
+    pub fn add_vertex(&mut self) -> usize {
+        let v = self.num_vert;
+        self.adj_mat.grow(v + 1);
+
+        if !self.vert_data.is_empty() {
+            self.vert_data.push(0);
+        }
+        if !self.vert_labels.is_empty() {
+            let label = std::iter::once(v.to_string())
+                .chain((1..).map(|i| format!("{}_{}", v, i)))
+                .find(|label| !self.label_indices.contains_key(label))
+                .expect("there is always an unused label");
+            self.label_indices.insert(label.clone(), v);
+            self.vert_labels.push(label);
+        }
+        self.num_vert += 1;
+        v
+    }
+
+    /// ## remove_vertex
+    ///
+    /// Remove vertex `v` with all of its edges. The indices of all vertices after `v` are
+    /// decreased by one, the same holds for `vert_data`, `vert_labels` and `edge_data`.
+    pub fn remove_vertex(&mut self, v: usize) {
+        self.assert_vertex(v);
+        let relabel = |i: usize| match i {
+            i if i < v => Some(i),
+            i if i == v => None,
+            i => Some(i - 1),
+        };
+
+        let empty = self.empty_adj_mat();
+        let adj_mat = std::mem::replace(&mut self.adj_mat, empty);
+        *self.adj_mat = adj_mat.relabel(self.num_vert - 1, relabel);
+
+        if v < self.vert_data.len() {
+            self.vert_data.remove(v);
+        }
+        if v < self.vert_labels.len() {
+            let mut labels = std::mem::take(&mut self.vert_labels);
+            labels.remove(v);
+            self.set_vert_labels(labels);
+        }
+        self.edge_data.retain(|e| e.from != v && e.to != v);
+        for e in self.edge_data.iter_mut() {
+            e.from = relabel(e.from).unwrap();
+            e.to = relabel(e.to).unwrap();
+        }
+        self.num_vert -= 1;
+    }
+
+    fn assert_vertex(&self, v: usize) {
+        assert!(v < self.num_vert, "vertex index out of bounds");
+    }
+
+    /// ## empty_adj_mat
+    ///
+    /// placeholder while the adjacency matrix is rebuilt, panics for an out-of-core matrix
+    /// before the Graph is changed
+    fn empty_adj_mat(&self) -> Box<AdjMat> {
+        if let AdjMat::Blocked(_m) = &*self.adj_mat {
+            panic!("out-of-core matrix can not be changed");
+        }
+        Box::new(AdjMat::from_edges(0, self.directedness, Vec::new()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::neighbors;
+    use crate::graph::{generators, Directedness, EdgeData, Graph, GraphBuilder};
+
+    #[test]
+    fn add_remove_edge() {
+        let mut g = Graph::from_edges(3, vec![(0, 1)]);
+        g.add_edge(1, 2);
+        g.add_edge(1, 2);
+        assert!(g.has_edge(1, 2), "edge was added");
+        assert!(!g.has_edge(2, 1), "directed edge has no reverse");
+        assert_eq!(
+            g.num_edges(),
+            2,
+            "adding an edge twice does not change the graph"
+        );
+
+        assert!(g.remove_edge(0, 1), "edge existed");
+        assert!(!g.remove_edge(0, 1), "edge was already removed");
+        assert_eq!(
+            g.edges().collect::<Vec<_>>(),
+            vec![(1, 2)],
+            "remaining edges"
+        );
+    }
+
+    #[test]
+    fn undirected() {
+        let mut g = generators::path(4);
+        g.add_edge(3, 0);
+        assert!(g.has_edge(0, 3), "reverse edge was added");
+        assert_eq!(g.num_edges(), 4, "undirected edges are counted once");
+        assert_eq!(
+            g.edges().collect::<Vec<_>>(),
+            vec![(0, 1), (0, 3), (1, 2), (2, 3)],
+            "edges of the cycle"
+        );
+        assert_eq!(g.degree(0), 2, "degree after adding an edge");
+
+        g.remove_edge(2, 1);
+        assert!(!g.has_edge(1, 2), "reverse edge was removed");
+        assert_eq!(g.degree(1), 1, "degree after removing an edge");
+        assert_eq!(
+            neighbors(&g),
+            vec![vec![1, 3], vec![0], vec![3], vec![0, 2]],
+            "neighbors of the changed graph"
+        );
+    }
+
+    #[test]
+    fn add_remove_vertex() {
+        let mut g = GraphBuilder::new(3)
+            .with_edges(vec![(0, 1), (1, 2), (2, 0)])
+            .with_directedness(Directedness::Undirected)
+            .build();
+        g.vert_data = vec![5, 6, 7];
+        g.set_vert_labels(vec![
+            String::from("a"),
+            String::from("b"),
+            String::from("c"),
+        ]);
+        g.edge_data = vec![
+            EdgeData {
+                from: 0,
+                to: 1,
+                weight: 1.,
+                timestamp: None,
+            },
+            EdgeData {
+                from: 1,
+                to: 2,
+                weight: 2.,
+                timestamp: None,
+            },
+            EdgeData {
+                from: 2,
+                to: 0,
+                weight: 3.,
+                timestamp: None,
+            },
+        ];
+
+        let v = g.add_vertex();
+        assert_eq!(v, 3, "index of the new vertex");
+        assert_eq!(g.num_vert(), 4, "number of vertices after adding");
+        assert_eq!(g.degree(v), 0, "new vertex is isolated");
+        assert_eq!(g.vert_data, vec![5, 6, 7, 0], "data of the new vertex");
+        assert_eq!(g.label_of(v), "3", "label of the new vertex");
+        g.add_edge(v, 2);
+
+        g.remove_vertex(1);
+        assert_eq!(g.num_vert(), 3, "number of vertices after removing");
+        assert_eq!(
+            neighbors(&g),
+            vec![vec![1], vec![0, 2], vec![1]],
+            "indices after the removed vertex are shifted"
+        );
+        assert_eq!(g.vert_data, vec![5, 7, 0], "vertex data is shifted");
+        assert_eq!(g.vert_labels(), ["a", "c", "3"], "labels are shifted");
+        assert_eq!(g.index_of_label("c"), Some(1), "label index is shifted");
+        assert_eq!(g.index_of_label("b"), None, "label is removed");
+        let edges: Vec<_> = g
+            .edge_data
+            .iter()
+            .map(|e| (e.from, e.to, e.weight))
+            .collect();
+        assert_eq!(edges, vec![(1, 0, 3.)], "edge data is shifted");
+    }
+
+    #[test]
+    fn unique_label() {
+        let mut g = generators::path(2);
+        g.set_vert_labels(vec![String::from("2"), String::from("2_1")]);
+        let v = g.add_vertex();
+        assert_eq!(
+            g.label_of(v),
+            "2_2",
+            "label of another vertex is not reused"
+        );
+        assert_eq!(g.index_of_label("2"), Some(0), "old label is unchanged");
+        assert_eq!(g.index_of_label("2_2"), Some(v), "new label is indexed");
+    }
+
+    #[test]
+    fn without_vertex_data() {
+        let mut g = generators::complete(4);
+        g.remove_vertex(0);
+        g.add_vertex();
+        assert!(g.vert_data.is_empty(), "no vertex data is created");
+        assert!(g.vert_labels().is_empty(), "no labels are created");
+        assert_eq!(g.num_edges(), 3, "triangle and an isolated vertex");
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex index out of bounds")]
+    fn out_of_bounds() {
+        let mut g = generators::path(3);
+        g.add_edge(0, 3);
+    }
+}
@@ -0,0 +1,81 @@
+/// # EdgePolicy
+///
+/// What happens to self loops or parallel edges while a Graph is built
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgePolicy {
+    /// the Graph is not built, `try_*` returns a `GraphError::Rejected`
+    Reject,
+    /// the edges are removed and counted in `DroppedEdges`
+    Drop,
+    /// the edges are stored. The multiplicity of kept parallel edges is only stored in the
+    /// adjacency matrix, every path and cycle count treats them as a single edge.
+    Keep,
+}
+
+/// # BuildPolicy
+///
+/// How self loops and parallel edges are handled by `GraphBuilder` and therefore by all
+/// loaders and generators. Self loops are never part of a `k`-path, but they add work to every
+/// iteration of `compute_walk_sum`.
+/// In an undirected Graph the edges `(u, v)` and `(v, u)` are parallel.
+///
+/// The adjacency matrix has one entry per edge, so if parallel edges are kept, their number
+/// is stored as the value of the entry. This multiplicity is ignored by all counting: the coded
+/// walk sums, the exact and color-coding counters and `neighbors_of` see each parallel edge
+/// once. By default self loops are kept and parallel edges are dropped.
+///
+/// Example:
+///
+/// ```no code
+/// let policy = BuildPolicy::new()
+///     .with_self_loops(EdgePolicy::Drop)
+///     .with_parallel_edges(EdgePolicy::Reject);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildPolicy {
+    pub self_loops: EdgePolicy,
+    pub parallel_edges: EdgePolicy,
+}
+
+impl BuildPolicy {
+    /// ## new
+    ///
+    /// Keep self loops and drop parallel edges
+    pub fn new() -> Self {
+        BuildPolicy {
+            self_loops: EdgePolicy::Keep,
+            parallel_edges: EdgePolicy::Drop,
+        }
+    }
+
+    /// ## with_self_loops
+    ///
+    /// Set how edges `(v, v)` are handled
+    pub fn with_self_loops(mut self, policy: EdgePolicy) -> Self {
+        self.self_loops = policy;
+        self
+    }
+
+    /// ## with_parallel_edges
+    ///
+    /// Set how an edge that was already added is handled
+    pub fn with_parallel_edges(mut self, policy: EdgePolicy) -> Self {
+        self.parallel_edges = policy;
+        self
+    }
+}
+
+impl Default for BuildPolicy {
+    fn default() -> Self {
+        BuildPolicy::new()
+    }
+}
+
+/// # DroppedEdges
+///
+/// The number of edges that were removed while a Graph was built, see `BuildPolicy`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DroppedEdges {
+    pub self_loops: usize,
+    pub parallel_edges: usize,
+}
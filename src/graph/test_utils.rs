@@ -0,0 +1,74 @@
+use crate::graph::Graph;
+use std::path::{Path, PathBuf};
+
+/// ## neighbors
+///
+/// the neighbors of every vertex of `g`, used to compare the edges of two graphs
+pub(crate) fn neighbors(g: &Graph) -> Vec<Vec<usize>> {
+    (0..g.num_vert())
+        .map(|i| g.neighbors_of(i).collect())
+        .collect()
+}
+
+/// # TempFile
+///
+/// A file in the temporary directory whose name is unique for every test process.
+/// The file is removed when the TempFile is dropped, also if the test panics.
+pub(crate) struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// ## new
+    ///
+    /// the path `extensor_<name>_<process id>` in the temporary directory, the file is not
+    /// created
+    pub(crate) fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("extensor_{}_{}", name, std::process::id()));
+        TempFile { path }
+    }
+
+    /// ## with_content
+    ///
+    /// create the temporary file `name` with the given `content`
+    pub(crate) fn with_content<C: AsRef<[u8]>>(name: &str, content: C) -> Self {
+        let file = TempFile::new(name);
+        file.write(content);
+        file
+    }
+
+    /// ## write
+    ///
+    /// replace the content of the file
+    pub(crate) fn write<C: AsRef<[u8]>>(&self, content: C) {
+        std::fs::write(&self.path, content).expect("could not write temporary file");
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn path_str(&self) -> &str {
+        self.path.to_str().expect("temporary path is not utf-8")
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // the file is not created by every test
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// ## from_content
+///
+/// write `content` to the temporary file `name` and read it with `load`. The file is removed
+/// afterwards, also if `load` panics.
+pub(crate) fn from_content<C, T, F>(name: &str, content: C, load: F) -> T
+where
+    C: AsRef<[u8]>,
+    F: FnOnce(&str) -> T,
+{
+    let file = TempFile::with_content(name, content);
+    load(file.path_str())
+}
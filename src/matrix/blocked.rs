@@ -0,0 +1,598 @@
+#[cfg(feature = "extensor_bitvec")]
+use crate::extensor::bitvec::ExTensor;
+#[cfg(feature = "extensor_dense_hashmap")]
+use crate::extensor::dense_hashmap::ExTensor;
+
+use crate::matrix::parallel::Parallelism;
+use num_traits::identities::Zero;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// number of u64 values in the header before the block offsets
+const HEADER_LEN: u64 = 4;
+/// number of bytes of one stored entry `(row, col)`
+const ENTRY_LEN: u64 = 16;
+/// maximal number of temporary block files that `create` keeps open at the same time
+const MAX_OPEN_BLOCKS: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct Matrix {
+    pub nrows: usize,
+    pub ncols: usize,
+    pub block_size: usize,
+    path: PathBuf,
+    offsets: Vec<u64>,
+    /// open handle of the file at `path`, shared by all clones of the Matrix
+    file: Arc<Mutex<File>>,
+}
+
+/// # Matrix
+///
+/// A 0/1 matrix that lives in a file instead of memory, used for graphs whose adjacency
+/// matrix does not fit into memory.
+/// The rows are split into blocks of `block_size` rows. A block only contains the positions
+/// `(row, col)` of its non zero entries and is read from the file whenever it is needed,
+/// so at most one block is in memory at the same time. The file stays open as long as the
+/// Matrix exists, and a single row is found by a binary search inside its block.
+///
+/// File layout (all values are little endian u64):
+///
+/// ```no code
+/// nrows ncols block_size num_blocks | offset_0 ... offset_num_blocks | row col row col ...
+/// ```
+///
+/// where the entries of block `b` are stored between `offset_b` and `offset_(b+1)`.
+impl Matrix {
+    /// ## create
+    ///
+    /// Write the non zero `entries` of a `nrows` x `ncols` matrix to the file at `path` and
+    /// return the Matrix that is backed by it. The entries can be given in any order,
+    /// duplicates are removed. Only one block is kept in memory while the file is written.
+    /// For this the entries are first written to a temporary file, which is then read once for
+    /// every batch of `MAX_OPEN_BLOCKS` blocks to collect each block of the batch in its own
+    /// temporary file. The temporary files are kept in a new directory next to `path`, whose
+    /// name is unique, so several matrices can be created in the same directory at the same
+    /// time. The directory is removed, even if `create` fails.
+    pub fn create<P, I>(
+        path: P,
+        nrows: usize,
+        ncols: usize,
+        block_size: usize,
+        entries: I,
+    ) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        assert!(block_size > 0, "block size must be positive");
+
+        let path = path.as_ref().to_path_buf();
+        let num_blocks = nrows.div_ceil(block_size);
+        let temp_dir = TempDir::new(&path)?;
+
+        // collect all entries in one temporary file, so they can be read once per batch
+        let entries_path = temp_dir.path.join("entries");
+        let mut entries_file = BufWriter::new(File::create(&entries_path)?);
+        for (row, col) in entries {
+            assert!(row < nrows && col < ncols, "index out of bounds");
+            write_entry(&mut entries_file, row, col)?;
+        }
+        entries_file.flush()?;
+        drop(entries_file);
+        let num_entries = fs::metadata(&entries_path)?.len() / ENTRY_LEN;
+
+        let mut file = BufWriter::new(File::create(&path)?);
+        for v in [nrows, ncols, block_size, num_blocks].iter() {
+            file.write_all(&(*v as u64).to_le_bytes())?;
+        }
+        // reserve space for the offsets, they are written once all blocks are known
+        for _ in 0..=num_blocks {
+            file.write_all(&0u64.to_le_bytes())?;
+        }
+
+        let mut offsets = vec![0u64];
+        for batch_start in (0..num_blocks).step_by(MAX_OPEN_BLOCKS) {
+            let batch_end = (batch_start + MAX_OPEN_BLOCKS).min(num_blocks);
+
+            // distribute the entries of this batch over one temporary file per block
+            let block_paths: Vec<_> = (batch_start..batch_end)
+                .map(|b| temp_dir.path.join(format!("block{}", b)))
+                .collect();
+            let mut block_files = block_paths
+                .iter()
+                .map(|p| File::create(p).map(BufWriter::new))
+                .collect::<io::Result<Vec<_>>>()?;
+
+            let mut entries_file = BufReader::new(File::open(&entries_path)?);
+            for _ in 0..num_entries {
+                let row = read_u64(&mut entries_file)? as usize;
+                let col = read_u64(&mut entries_file)? as usize;
+                let b = row / block_size;
+                if batch_start <= b && b < batch_end {
+                    write_entry(&mut block_files[b - batch_start], row, col)?;
+                }
+            }
+            for f in block_files.iter_mut() {
+                f.flush()?;
+            }
+            drop(block_files);
+
+            // sort every block and append it to the matrix file
+            for block_path in block_paths.iter() {
+                let len = fs::metadata(block_path)?.len();
+                let mut block = read_entries(&mut BufReader::new(File::open(block_path)?), len)?;
+                block.sort_unstable();
+                block.dedup();
+
+                for (row, col) in block.iter() {
+                    write_entry(&mut file, *row, *col)?;
+                }
+                offsets.push(offsets[offsets.len() - 1] + block.len() as u64);
+                fs::remove_file(block_path)?;
+            }
+        }
+
+        let mut file = file.into_inner()?;
+        file.seek(SeekFrom::Start(HEADER_LEN * 8))?;
+        for o in offsets.iter() {
+            file.write_all(&o.to_le_bytes())?;
+        }
+        drop(file);
+
+        Ok(Matrix {
+            nrows,
+            ncols,
+            block_size,
+            file: Arc::new(Mutex::new(File::open(&path)?)),
+            path,
+            offsets,
+        })
+    }
+
+    /// ## open
+    ///
+    /// Open a Matrix that was written by `create` before.
+    /// Returns an `InvalidData` error if the header does not match the size of the matrix, the
+    /// block offsets are not increasing or the file does not end after the last block.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = BufReader::new(File::open(&path)?);
+        let corrupt = |message| Err(io::Error::new(io::ErrorKind::InvalidData, message));
+
+        let nrows = read_u64(&mut file)? as usize;
+        let ncols = read_u64(&mut file)? as usize;
+        let block_size = read_u64(&mut file)? as usize;
+        let num_blocks = read_u64(&mut file)? as usize;
+        if block_size == 0 || num_blocks != nrows.div_ceil(block_size) {
+            return corrupt("header of blocked matrix is corrupt");
+        }
+
+        let offsets = (0..=num_blocks)
+            .map(|_| read_u64(&mut file))
+            .collect::<io::Result<Vec<_>>>()?;
+        if offsets[0] != 0 || offsets.windows(2).any(|w| w[0] > w[1]) {
+            return corrupt("block offsets of blocked matrix are not increasing");
+        }
+
+        let m = Matrix {
+            nrows,
+            ncols,
+            block_size,
+            path,
+            offsets,
+            file: Arc::new(Mutex::new(file.into_inner())),
+        };
+        let len = m.lock_file().metadata()?.len();
+        let expected = m.nnz() as u64;
+        if len < m.entry_position(0) || (len - m.entry_position(0)) / ENTRY_LEN != expected {
+            return corrupt("length of blocked matrix file does not match the block offsets");
+        }
+
+        Ok(m)
+    }
+
+    /// ## num_blocks
+    ///
+    /// Returns the number of row blocks
+    pub fn num_blocks(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// ## nnz
+    ///
+    /// Returns the number of non zero entries
+    pub fn nnz(&self) -> usize {
+        self.offsets[self.offsets.len() - 1] as usize
+    }
+
+    /// ## read_block
+    ///
+    /// Read the positions `(row, col)` of all non zero entries in block `b`, sorted by row.
+    pub fn read_block(&self, b: usize) -> io::Result<Vec<(usize, usize)>> {
+        let mut file = self.lock_file();
+        self.read_range(&mut file, self.offsets[b], self.offsets[b + 1])
+    }
+
+    /// ## neighbors_of
+    ///
+    /// Return the column indices of all non zero entries in row `i`.
+    /// The entries of row `i` are found by a binary search inside the block of row `i`, so only
+    /// `O(log(block_size))` entries besides the row itself are read from the file.
+    pub(crate) fn neighbors_of(&self, i: usize) -> io::Result<Vec<usize>> {
+        let b = i / self.block_size;
+        let mut file = self.lock_file();
+        let start =
+            self.partition_point(&mut file, self.offsets[b], self.offsets[b + 1], |r| r < i)?;
+        let end = self.partition_point(&mut file, start, self.offsets[b + 1], |r| r <= i)?;
+
+        Ok(self
+            .read_range(&mut file, start, end)?
+            .into_iter()
+            .map(|(_row, col)| col)
+            .collect())
+    }
+
+    /// ## mul_coded
+    ///
+    /// Multiply the matrix where every row `i` is multiplied with `coding[i]`, with the vector `rhs`.
+    /// The blocks are read one after another, so besides `coding` and `rhs` only the result
+    /// and one block are in memory. Within a block the rows are distributed over `parallelism`.
+    pub(crate) fn mul_coded(
+        &self,
+        coding: &[ExTensor],
+        rhs: &[ExTensor],
+        parallelism: &Parallelism,
+    ) -> io::Result<Vec<ExTensor>> {
+        let mut res = Vec::with_capacity(self.nrows);
+
+        for b in 0..self.num_blocks() {
+            let start = b * self.block_size;
+            let end = (start + self.block_size).min(self.nrows);
+
+            // every value in row i is coding[i], so the row can first be summed up and
+            // then multiplied once with coding[i]
+            let mut sums = vec![ExTensor::zero(); end - start];
+            for (row, col) in self.read_block(b)? {
+                sums[row - start] = &sums[row - start] + &rhs[col];
+            }
+
+            res.extend(parallelism.map_rows(end - start, |r| &coding[start + r] * &sums[r]));
+        }
+
+        Ok(res)
+    }
+
+    /// ## triplets
+    ///
+    /// Read all non zero entries as `(row, col, 1)` triplets
+    pub(crate) fn triplets(&self) -> io::Result<Vec<(usize, usize, u8)>> {
+        let mut res = Vec::with_capacity(self.nnz());
+
+        for b in 0..self.num_blocks() {
+            res.extend(self.read_block(b)?.into_iter().map(|(i, j)| (i, j, 1)));
+        }
+
+        Ok(res)
+    }
+
+    /// ## lock_file
+    ///
+    /// the open file, every read seeks to its position first, so a poisoned lock can be reused
+    fn lock_file(&self) -> MutexGuard<'_, File> {
+        self.file.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// ## read_range
+    ///
+    /// read the entries with the indices `start..end`
+    fn read_range(&self, file: &mut File, start: u64, end: u64) -> io::Result<Vec<(usize, usize)>> {
+        file.seek(SeekFrom::Start(self.entry_position(start)))?;
+        read_entries(&mut BufReader::new(file), (end - start) * ENTRY_LEN)
+    }
+
+    /// ## partition_point
+    ///
+    /// the index of the first entry in `start..end` whose row does not satisfy `pred`,
+    /// where the entries in `start..end` are sorted by row
+    fn partition_point<P>(&self, file: &mut File, start: u64, end: u64, pred: P) -> io::Result<u64>
+    where
+        P: Fn(usize) -> bool,
+    {
+        let (mut lo, mut hi) = (start, end);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            file.seek(SeekFrom::Start(self.entry_position(mid)))?;
+            if pred(read_u64(file)? as usize) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        Ok(lo)
+    }
+
+    /// ## entry_position
+    ///
+    /// the byte position of the entry with index `index` in the file
+    fn entry_position(&self, index: u64) -> u64 {
+        (HEADER_LEN + self.offsets.len() as u64) * 8 + index * ENTRY_LEN
+    }
+}
+
+impl PartialEq for Matrix {
+    fn eq(&self, other: &Self) -> bool {
+        self.nrows == other.nrows
+            && self.ncols == other.ncols
+            && self.block_size == other.block_size
+            && self.path == other.path
+            && self.offsets == other.offsets
+    }
+}
+
+impl Eq for Matrix {}
+
+/// # TempDir
+///
+/// The directory for the temporary files of `Matrix::create`. It is removed together with its
+/// files when the TempDir is dropped, so they do not stay on disk if `create` returns early or
+/// panics.
+struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// ## new
+    ///
+    /// create the directory `<file name of path>.tmp<process id>_<counter>` next to `path`.
+    /// The counter is increased until the directory does not exist yet.
+    fn new(path: &Path) -> io::Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("matrix");
+        loop {
+            let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let dir = path.with_file_name(format!(
+                "{}.tmp{}_{}",
+                file_name,
+                std::process::id(),
+                counter
+            ));
+            match fs::create_dir(&dir) {
+                Ok(()) => return Ok(TempDir { path: dir }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // nothing can be done if the directory can not be removed
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn write_entry<W: Write>(w: &mut W, row: usize, col: usize) -> io::Result<()> {
+    w.write_all(&(row as u64).to_le_bytes())?;
+    w.write_all(&(col as u64).to_le_bytes())
+}
+
+fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut buffer = [0u8; 8];
+    r.read_exact(&mut buffer)?;
+    Ok(u64::from_le_bytes(buffer))
+}
+
+/// read `len` bytes of `(row, col)` entries
+fn read_entries<R: Read>(r: &mut R, len: u64) -> io::Result<Vec<(usize, usize)>> {
+    let num_entries = len / ENTRY_LEN;
+    let mut res = Vec::with_capacity(num_entries as usize);
+
+    for _ in 0..num_entries {
+        let row = read_u64(r)? as usize;
+        let col = read_u64(r)? as usize;
+        res.push((row, col));
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::test_utils::TempFile;
+    use crate::matrix::{blocked::Matrix, parallel::Parallelism, sparse_triples};
+    use crate::utils;
+    use std::path::Path;
+
+    /// number of temporary directories of `Matrix::create` next to `path`
+    fn temp_dirs(path: &Path) -> usize {
+        let prefix = format!("{}.tmp", path.file_name().unwrap().to_str().unwrap());
+        std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter(|e| {
+                let name = e.as_ref().unwrap().file_name();
+                name.to_str().unwrap().starts_with(&prefix)
+            })
+            .count()
+    }
+
+    #[test]
+    fn create_and_open() {
+        let file = TempFile::new("blocked_create_and_open");
+        let path = file.path();
+        let entries = vec![(4, 1), (0, 1), (2, 3), (0, 1), (1, 0), (4, 4)];
+        let m = Matrix::create(path, 5, 5, 2, entries).unwrap();
+
+        assert_eq!(m.num_blocks(), 3, "5 rows in blocks of 2");
+        assert_eq!(m.nnz(), 5, "duplicates are removed");
+        assert_eq!(m.read_block(0).unwrap(), vec![(0, 1), (1, 0)]);
+        assert_eq!(m.read_block(1).unwrap(), vec![(2, 3)]);
+        assert_eq!(m.read_block(2).unwrap(), vec![(4, 1), (4, 4)]);
+        assert_eq!(m.neighbors_of(4).unwrap(), vec![1, 4]);
+        assert_eq!(m.neighbors_of(3).unwrap(), Vec::<usize>::new());
+
+        let opened = Matrix::open(path).unwrap();
+        assert_eq!(opened, m, "opened matrix matches the created one");
+    }
+
+    #[test]
+    fn many_blocks() {
+        // more blocks than temporary files are open at the same time
+        let file = TempFile::new("blocked_many_blocks");
+        let path = file.path();
+        let n = 2 * super::MAX_OPEN_BLOCKS + 3;
+        let entries: Vec<_> = (0..n).rev().map(|i| (i, (i + 1) % n)).collect();
+        let m = Matrix::create(path, n, n, 1, entries).unwrap();
+
+        assert_eq!(m.num_blocks(), n, "one row per block");
+        assert_eq!(m.nnz(), n);
+        for i in [0, super::MAX_OPEN_BLOCKS, n - 1].iter() {
+            assert_eq!(
+                m.neighbors_of(*i).unwrap(),
+                vec![(i + 1) % n],
+                "row is found in every batch"
+            );
+        }
+        assert_eq!(temp_dirs(path), 0, "temporary files are removed");
+    }
+
+    #[test]
+    fn temp_files_removed_on_panic() {
+        let file = TempFile::new("blocked_temp_files");
+        let path = file.path();
+        let res = std::panic::catch_unwind(|| {
+            Matrix::create(path, 3, 3, 1, vec![(0, 1), (3, 0)]).unwrap();
+        });
+        assert!(res.is_err(), "entry out of bounds");
+        assert_eq!(temp_dirs(path), 0, "temporary files are removed");
+        assert!(!path.exists(), "matrix file is not written yet");
+    }
+
+    #[test]
+    fn open_corrupt() {
+        let file = TempFile::new("blocked_open_corrupt");
+        let path = file.path();
+        Matrix::create(path, 4, 4, 2, vec![(0, 1), (2, 3), (3, 0)]).unwrap();
+        let content = std::fs::read(path).unwrap();
+        let corrupt = |content: &[u8]| {
+            file.write(content);
+            Matrix::open(path).map(|_m| ()).unwrap_err().kind()
+        };
+
+        // offsets 0, 1, 3 follow the 4 header values
+        let mut decreasing = content.clone();
+        decreasing[5 * 8] = 4;
+        assert_eq!(
+            corrupt(&decreasing),
+            std::io::ErrorKind::InvalidData,
+            "offsets must be increasing"
+        );
+        assert_eq!(
+            corrupt(&content[..content.len() - 1]),
+            std::io::ErrorKind::InvalidData,
+            "last block is truncated"
+        );
+        let mut longer = content.clone();
+        longer.extend_from_slice(&[0; 16]);
+        assert_eq!(
+            corrupt(&longer),
+            std::io::ErrorKind::InvalidData,
+            "data after the last block"
+        );
+        assert_eq!(
+            corrupt(&content[..20]),
+            std::io::ErrorKind::UnexpectedEof,
+            "header is truncated"
+        );
+
+        file.write(&content);
+        assert!(Matrix::open(path).is_ok(), "original file is valid");
+    }
+
+    #[test]
+    fn same_file_stem() {
+        // the temporary files of both matrices must not collide
+        let files = [
+            TempFile::new("blocked_same_stem.a"),
+            TempFile::new("blocked_same_stem.b"),
+        ];
+        let n = 300;
+        let handles: Vec<_> = files
+            .iter()
+            .enumerate()
+            .map(|(shift, file)| {
+                let path = file.path().to_path_buf();
+                std::thread::spawn(move || {
+                    let entries: Vec<_> = (0..n).map(|i| (i, (i + shift + 1) % n)).collect();
+                    Matrix::create(path, n, n, 1, entries).unwrap()
+                })
+            })
+            .collect();
+        let matrices: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        for (shift, m) in matrices.iter().enumerate() {
+            for i in 0..n {
+                assert_eq!(
+                    m.neighbors_of(i).unwrap(),
+                    vec![(i + shift + 1) % n],
+                    "entries of the matrix are not mixed up"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_in_block() {
+        let file = TempFile::new("blocked_neighbors_in_block");
+        let path = file.path();
+        let entries = vec![(0, 2), (1, 0), (1, 3), (2, 2), (3, 0), (3, 1), (3, 2)];
+        let m = Matrix::create(path, 4, 4, 4, entries).unwrap();
+        let neighbors: Vec<_> = (0..4).map(|i| m.neighbors_of(i).unwrap()).collect();
+        assert_eq!(
+            neighbors,
+            vec![vec![2], vec![0, 3], vec![2], vec![0, 1, 2]],
+            "rows are found inside of one block"
+        );
+    }
+
+    #[test]
+    fn mul_coded() {
+        let file = TempFile::new("blocked_mul_coded");
+        let path = file.path();
+        let n = 5;
+        let k = 3;
+        let values = vec![
+            0, 1, 0, 0, 1, //
+            1, 0, 1, 0, 0, //
+            0, 1, 0, 1, 0, //
+            0, 0, 1, 0, 1, //
+            1, 0, 0, 1, 0, //
+        ];
+        let coding = utils::create_vandermonde(n, k);
+
+        let full: sparse_triples::Matrix<u8> = sparse_triples::Matrix::new(n, n, values);
+        let entries = full.data.iter().map(|(i, j, _v)| (*i, *j));
+        let blocked = Matrix::create(path, n, n, 2, entries).unwrap();
+
+        let expect = &full.add_coding(&coding) * coding.clone();
+        let res = blocked
+            .mul_coded(&coding, &coding, &Parallelism::serial())
+            .unwrap();
+
+        for (e, r) in expect.into_iter().zip(res) {
+            assert_eq!(
+                e.terms(),
+                r.terms(),
+                "blocked matrix should behave like the full matrix"
+            );
+        }
+    }
+}
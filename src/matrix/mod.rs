@@ -1,3 +1,6 @@
+pub mod blocked;
 pub mod naive;
+pub mod parallel;
 pub mod sparse_hash;
 pub mod sparse_triples;
+pub mod symmetric;
@@ -3,6 +3,8 @@ use crate::extensor::bitvec::ExTensor;
 #[cfg(feature = "extensor_dense_hashmap")]
 use crate::extensor::dense_hashmap::ExTensor;
 
+use crate::matrix::parallel::Parallelism;
+use crate::matrix::{sparse_hash, sparse_triples, symmetric};
 use num_traits::identities::{One, Zero};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -48,6 +50,109 @@ where
 
         Matrix { nrows, ncols, data }
     }
+
+    /// ## from_triplets
+    ///
+    /// Create a new Matrix from an iterator over `(row, col, value)` triplets.
+    /// Entries that are not contained in `triplets` are zero. If an entry is given
+    /// multiple times, the last value is used.
+    pub fn from_triplets<I>(nrows: usize, ncols: usize, triplets: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, T)>,
+    {
+        let mut data = vec![T::zero(); nrows * ncols];
+
+        for (i, j, val) in triplets {
+            assert!(i < nrows && j < ncols, "index out of bounds");
+            data[i * ncols + j] = val;
+        }
+
+        Matrix { nrows, ncols, data }
+    }
+
+    /// ## nnz
+    ///
+    /// Returns the number of non zero entries
+    pub fn nnz(&self) -> usize {
+        self.data.iter().filter(|v| !v.is_zero()).count()
+    }
+
+    /// ## to_dense
+    ///
+    /// Returns all `nrows*ncols` values in row major order
+    pub fn to_dense(&self) -> Vec<T> {
+        self.data.clone()
+    }
+
+    /// ## set
+    ///
+    /// Set the entry `(i, j)` to `val`
+    pub fn set(&mut self, i: usize, j: usize, val: T) {
+        assert!(i < self.nrows && j < self.ncols, "index out of bounds");
+        self.data[i * self.ncols + j] = val;
+    }
+
+    /// ## grow
+    ///
+    /// Add zero rows and columns, so the Matrix has `nrows` rows and `ncols` columns.
+    /// New columns move every row, so this is `O(nrows * ncols)`.
+    pub fn grow(&mut self, nrows: usize, ncols: usize) {
+        assert!(
+            nrows >= self.nrows && ncols >= self.ncols,
+            "a Matrix can not shrink"
+        );
+        if ncols > self.ncols {
+            let mut data = Vec::with_capacity(nrows * ncols);
+            for row in self.data.chunks(self.ncols.max(1)).take(self.nrows) {
+                data.extend(row.iter().cloned());
+                data.resize(data.len() + ncols - self.ncols, T::zero());
+            }
+            self.data = data;
+        }
+        self.data.resize(nrows * ncols, T::zero());
+        self.nrows = nrows;
+        self.ncols = ncols;
+    }
+
+    /// ## into_triplets
+    ///
+    /// Consume the Matrix and iterate over its non zero entries as `(row, col, value)` triplets
+    pub(crate) fn into_triplets(self) -> impl Iterator<Item = (usize, usize, T)> {
+        let ncols = self.ncols;
+
+        self.data
+            .into_iter()
+            .enumerate()
+            .filter(|(_i, val)| !val.is_zero())
+            .map(move |(i, val)| (i / ncols, i % ncols, val))
+    }
+}
+
+impl<T> From<sparse_hash::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: sparse_hash::Matrix<T>) -> Self {
+        Matrix::from_triplets(m.nrows, m.ncols, m.into_triplets())
+    }
+}
+
+impl<T> From<sparse_triples::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: sparse_triples::Matrix<T>) -> Self {
+        Matrix::from_triplets(m.nrows, m.ncols, m.data)
+    }
+}
+
+impl<T> From<symmetric::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: symmetric::Matrix<T>) -> Self {
+        Matrix::from_triplets(m.nrows, m.ncols, m.into_triplets())
+    }
 }
 
 impl Matrix<u8> {
@@ -58,7 +163,8 @@ impl Matrix<u8> {
 
         for (i, v) in self.data.iter().enumerate() {
             let row_index = i / self.ncols;
-            if *v == 1 {
+            // kept parallel edges are stored with their multiplicity, but coded like one edge
+            if *v != 0 {
                 data.push(coding[row_index].clone());
             } else {
                 data.push(ExTensor::zero());
@@ -72,20 +178,22 @@ impl Matrix<u8> {
         }
     }
 
-    pub(crate) fn neighbors_of(&self, i: usize) -> Vec<usize> {
-        let mut res = vec![];
-
+    /// ## neighbors_of
+    ///
+    /// Iterate over the column indices of all non zero entries in row `i`.
+    /// The matrix is dense, so the whole row (`ncols` entries) has to be scanned, which takes
+    /// `O(ncols)` instead of `O(degree)`. The naive backend is the dense reference implementation
+    /// and is exempt from the `O(degree)` guarantee of the sparse backends on purpose: caching
+    /// the row adjacency lists would duplicate the pattern it stores densely.
+    pub(crate) fn neighbors_of(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
         let start = i * self.ncols;
         let end = start + self.ncols;
-        let row = self.data[start..end].to_vec();
-
-        for (i, val) in row.iter().enumerate() {
-            if *val != 0 {
-                res.push(i);
-            }
-        }
 
-        res
+        self.data[start..end]
+            .iter()
+            .enumerate()
+            .filter(|(_j, val)| **val != 0)
+            .map(|(j, _val)| j)
     }
 }
 
@@ -116,6 +224,30 @@ where
     }
 }
 
+impl<T> Matrix<T>
+where
+    T: Zero + Clone + std::ops::Mul<Output = T> + Send + Sync,
+{
+    /// ## par_mul
+    ///
+    /// Multiply the Matrix with the vector `rhs`. The rows are distributed over the
+    /// threads of `parallelism`, the result is the same as for the serial multiplication.
+    pub fn par_mul(&self, rhs: &[T], parallelism: &Parallelism) -> Vec<T> {
+        assert_eq!(
+            self.ncols,
+            rhs.len(),
+            "dimensions of vector and matrix do not match"
+        );
+
+        parallelism.map_rows(self.nrows, |i| {
+            let row = &self.data[i * self.ncols..(i + 1) * self.ncols];
+            row.iter()
+                .zip(rhs.iter())
+                .fold(T::zero(), |acc, (v, x)| acc + v.clone() * x.clone())
+        })
+    }
+}
+
 impl<T> std::ops::Index<(usize, usize)> for Matrix<T> {
     type Output = T;
 
@@ -137,8 +269,9 @@ mod tests {
     #[cfg(feature = "extensor_dense_hashmap")]
     use crate::extensor::dense_hashmap::ExTensor;
 
-    use crate::matrix::naive::Matrix;
+    use crate::matrix::{naive::Matrix, sparse_hash, sparse_triples};
 
+    use crate::matrix::parallel::Parallelism;
     use crate::utils;
     use num_traits::identities::Zero;
 
@@ -230,18 +363,70 @@ mod tests {
     #[test]
     fn neighbors() {
         let m: Matrix<u8> = Matrix::new(4, 4, vec![0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 1, 1, 0, 0, 0, 0]);
-        let neighbors_0 = m.neighbors_of(0);
+        let neighbors_0: Vec<_> = m.neighbors_of(0).collect();
         let expect_0 = vec![1];
-        let neighbors_1 = m.neighbors_of(1);
+        let neighbors_1: Vec<_> = m.neighbors_of(1).collect();
         let expect_1 = vec![0, 2];
-        let neighbors_2 = m.neighbors_of(2);
+        let neighbors_2: Vec<_> = m.neighbors_of(2).collect();
         let expect_2 = vec![1, 2, 3];
-        let neighbors_3 = m.neighbors_of(3);
-        let expect_3 = vec![];
+        let neighbors_3: Vec<_> = m.neighbors_of(3).collect();
+        let expect_3: Vec<usize> = vec![];
 
         assert_eq!(neighbors_0, expect_0);
         assert_eq!(neighbors_1, expect_1);
         assert_eq!(neighbors_2, expect_2);
         assert_eq!(neighbors_3, expect_3);
     }
+
+    #[test]
+    fn from_triplets() {
+        let m = Matrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 3), (0, 1, 4)]);
+        let expect = Matrix::new(2, 3, vec![0, 4, 0, 0, 0, 3]);
+        assert_eq!(m, expect, "last value of a duplicate entry is used");
+        assert_eq!(m.nnz(), 2, "number of non zero entries");
+        assert_eq!(m.to_dense(), vec![0, 4, 0, 0, 0, 3], "dense values");
+    }
+
+    #[test]
+    fn set() {
+        let mut m = Matrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 3)]);
+        m.set(1, 0, 5);
+        m.set(0, 1, 4);
+        m.set(1, 2, 0);
+        assert_eq!(m.to_dense(), vec![0, 4, 0, 5, 0, 0], "entries are set");
+        assert_eq!(m.nnz(), 2, "zero entries are removed");
+    }
+
+    #[test]
+    fn grow() {
+        let mut m = Matrix::from_triplets(2, 2, vec![(0, 1, 2), (1, 0, 3)]);
+        m.grow(3, 3);
+        m.set(2, 2, 4);
+        assert_eq!(
+            m.to_dense(),
+            vec![0, 2, 0, 3, 0, 0, 0, 0, 4],
+            "entries are kept and new entries can be set"
+        );
+        assert_eq!(m.nnz(), 3, "no entries are added");
+    }
+
+    #[test]
+    fn convert() {
+        let m = Matrix::new(3, 2, vec![1, 0, 0, 2, 3, 0]);
+        let hash: sparse_hash::Matrix<i32> = m.clone().into();
+        let triples: sparse_triples::Matrix<i32> = m.clone().into();
+        assert_eq!(Matrix::from(hash), m, "round trip via sparse_hash");
+        assert_eq!(Matrix::from(triples), m, "round trip via sparse_triples");
+    }
+
+    #[test]
+    fn par_mul() {
+        let m = Matrix::new(3, 3, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let v = vec![1, 2, 3];
+        for num_threads in 1..=4 {
+            let parallelism = Parallelism::new(num_threads).with_min_rows_per_thread(1);
+            let r = m.par_mul(&v, &parallelism);
+            assert_eq!(r, vec![14, 32, 50], "parallel Matrix Vector multiplication");
+        }
+    }
 }
@@ -0,0 +1,148 @@
+/// # Parallelism
+///
+/// Configuration of the threads that are used for a parallel matrix vector multiplication.
+/// The rows of the matrix are split into contiguous blocks, one per thread, and every row is
+/// computed exactly like in the serial multiplication. Therefore the result does not depend
+/// on the number of threads.
+/// No threads are kept alive between two multiplications: every `map_rows` spawns scoped
+/// threads for its blocks and joins them before it returns. The default is serial.
+/// If a thread would get less than `min_rows_per_thread` rows, fewer threads are used, so small
+/// matrices are multiplied serially.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parallelism {
+    pub num_threads: usize,
+    pub min_rows_per_thread: usize,
+}
+
+impl Parallelism {
+    /// ## new
+    ///
+    /// Use up to `num_threads` threads
+    pub fn new(num_threads: usize) -> Self {
+        assert!(num_threads > 0, "at least one thread is needed");
+
+        Parallelism {
+            num_threads,
+            min_rows_per_thread: 32,
+        }
+    }
+
+    /// ## serial
+    ///
+    /// Compute everything on the current thread
+    pub fn serial() -> Self {
+        Parallelism::new(1)
+    }
+
+    /// ## available
+    ///
+    /// Use as many threads as there are cpus available
+    pub fn available() -> Self {
+        let num_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Parallelism::new(num_threads)
+    }
+
+    /// ## with_min_rows_per_thread
+    ///
+    /// Set the minimal number of rows a thread has to compute before another thread is used
+    pub fn with_min_rows_per_thread(mut self, min_rows_per_thread: usize) -> Self {
+        self.min_rows_per_thread = min_rows_per_thread.max(1);
+        self
+    }
+
+    /// ## threads_for
+    ///
+    /// the number of threads that are used for a matrix with `nrows` rows
+    fn threads_for(&self, nrows: usize) -> usize {
+        (nrows / self.min_rows_per_thread.max(1))
+            .min(self.num_threads)
+            .max(1)
+    }
+
+    /// ## map_rows
+    ///
+    /// compute `row(i)` for all `i` in `0..nrows` and collect the results in order
+    pub(crate) fn map_rows<T, F>(&self, nrows: usize, row: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(usize) -> T + Sync,
+    {
+        let num_threads = self.threads_for(nrows);
+        if num_threads == 1 {
+            return (0..nrows).map(row).collect();
+        }
+
+        let block_size = nrows.div_ceil(num_threads);
+        let row = &row;
+
+        std::thread::scope(|s| {
+            let handles: Vec<_> = (0..nrows)
+                .step_by(block_size)
+                .map(|start| {
+                    let end = (start + block_size).min(nrows);
+                    s.spawn(move || (start..end).map(row).collect::<Vec<_>>())
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("matrix vector thread panicked"))
+                .collect()
+        })
+    }
+}
+
+impl Default for Parallelism {
+    /// compute everything on the current thread, see `serial`
+    fn default() -> Self {
+        Parallelism::serial()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::parallel::Parallelism;
+
+    #[test]
+    fn threads_for() {
+        let parallelism = Parallelism::new(4).with_min_rows_per_thread(10);
+        assert_eq!(
+            parallelism.threads_for(5),
+            1,
+            "small matrices are computed serially"
+        );
+        assert_eq!(
+            parallelism.threads_for(25),
+            2,
+            "every thread gets enough rows"
+        );
+        assert_eq!(
+            parallelism.threads_for(1000),
+            4,
+            "not more threads than configured"
+        );
+    }
+
+    #[test]
+    fn serial_by_default() {
+        assert_eq!(
+            Parallelism::default(),
+            Parallelism::serial(),
+            "parallelism is opt-in"
+        );
+        assert!(Parallelism::available().num_threads >= 1);
+    }
+
+    #[test]
+    fn map_rows() {
+        let expect: Vec<_> = (0..100).map(|i| i * i).collect();
+        for num_threads in 1..=8 {
+            let parallelism = Parallelism::new(num_threads).with_min_rows_per_thread(1);
+            let res = parallelism.map_rows(100, |i| i * i);
+            assert_eq!(res, expect, "rows are returned in order");
+        }
+    }
+}
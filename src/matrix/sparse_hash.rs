@@ -3,6 +3,8 @@ use crate::extensor::bitvec::ExTensor;
 #[cfg(feature = "extensor_dense_hashmap")]
 use crate::extensor::dense_hashmap::ExTensor;
 
+use crate::matrix::parallel::Parallelism;
+use crate::matrix::{naive, sparse_triples, symmetric};
 use num_traits::identities::{One, Zero};
 use std::collections::HashMap;
 
@@ -64,9 +66,135 @@ where
         Matrix { nrows, ncols, data }
     }
 
-    pub(crate) fn from(nrows: usize, ncols: usize, data: HashMap<usize, Vec<(usize, T)>>) -> Self {
+    pub(crate) fn from_rows(
+        nrows: usize,
+        ncols: usize,
+        data: HashMap<usize, Vec<(usize, T)>>,
+    ) -> Self {
         Matrix { nrows, ncols, data }
     }
+
+    /// ## from_triplets
+    ///
+    /// Create a new Matrix from an iterator over `(row, col, value)` triplets, without
+    /// allocating a `nrows*ncols` buffer. Zero values are skipped. If an entry is given
+    /// multiple times, the last value is used.
+    pub fn from_triplets<I>(nrows: usize, ncols: usize, triplets: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, T)>,
+    {
+        let mut data: HashMap<usize, Vec<(usize, T)>> = HashMap::new();
+
+        for (i, j, val) in triplets {
+            assert!(i < nrows && j < ncols, "index out of bounds");
+            data.entry(i).or_default().push((j, val));
+        }
+
+        for row in data.values_mut() {
+            // stable sort keeps the last given value of a duplicate at the end
+            row.sort_by_key(|(j, _val)| *j);
+            row.reverse();
+            row.dedup_by_key(|(j, _val)| *j);
+            row.reverse();
+            row.retain(|(_j, val)| !val.is_zero());
+        }
+        data.retain(|_i, row| !row.is_empty());
+
+        Matrix { nrows, ncols, data }
+    }
+
+    /// ## nnz
+    ///
+    /// Returns the number of stored non zero entries
+    pub fn nnz(&self) -> usize {
+        self.data.values().map(|row| row.len()).sum()
+    }
+
+    /// ## to_dense
+    ///
+    /// Returns all `nrows*ncols` values in row major order
+    pub fn to_dense(&self) -> Vec<T> {
+        let mut res = vec![T::zero(); self.nrows * self.ncols];
+
+        for (i, row) in self.data.iter() {
+            for (j, val) in row.iter() {
+                res[i * self.ncols + j] = val.clone();
+            }
+        }
+
+        res
+    }
+
+    /// ## set
+    ///
+    /// Set the entry `(i, j)` to `val`. Setting an entry to zero removes it.
+    pub fn set(&mut self, i: usize, j: usize, val: T) {
+        assert!(i < self.nrows && j < self.ncols, "index out of bounds");
+
+        let row = self.data.entry(i).or_default();
+        match row.binary_search_by_key(&j, |(y, _v)| *y) {
+            Ok(pos) if val.is_zero() => {
+                row.remove(pos);
+            }
+            Ok(pos) => row[pos].1 = val,
+            Err(_pos) if val.is_zero() => {}
+            Err(pos) => row.insert(pos, (j, val)),
+        }
+        if row.is_empty() {
+            self.data.remove(&i);
+        }
+    }
+
+    /// ## grow
+    ///
+    /// Add empty rows and columns, so the Matrix has `nrows` rows and `ncols` columns.
+    /// The stored entries are not changed.
+    pub fn grow(&mut self, nrows: usize, ncols: usize) {
+        assert!(
+            nrows >= self.nrows && ncols >= self.ncols,
+            "a Matrix can not shrink"
+        );
+        self.nrows = nrows;
+        self.ncols = ncols;
+    }
+
+    /// ## into_triplets
+    ///
+    /// Consume the Matrix and iterate over its non zero entries as `(row, col, value)` triplets
+    pub(crate) fn into_triplets(self) -> impl Iterator<Item = (usize, usize, T)> {
+        self.data
+            .into_iter()
+            .flat_map(|(i, row)| row.into_iter().map(move |(j, val)| (i, j, val)))
+    }
+}
+
+impl<T> From<naive::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: naive::Matrix<T>) -> Self {
+        let (nrows, ncols) = (m.nrows, m.ncols);
+        Matrix::from_triplets(nrows, ncols, m.into_triplets())
+    }
+}
+
+impl<T> From<sparse_triples::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: sparse_triples::Matrix<T>) -> Self {
+        Matrix::from_triplets(m.nrows, m.ncols, m.data)
+    }
+}
+
+impl<T> From<symmetric::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: symmetric::Matrix<T>) -> Self {
+        let (nrows, ncols) = (m.nrows, m.ncols);
+        Matrix::from_triplets(nrows, ncols, m.into_triplets())
+    }
 }
 
 impl Matrix<u8> {
@@ -81,16 +209,18 @@ impl Matrix<u8> {
             data.insert(*from, v);
         }
 
-        Matrix::from(self.nrows, self.ncols, data)
+        Matrix::from_rows(self.nrows, self.ncols, data)
     }
 
-    pub(crate) fn neighbors_of(&self, i: usize) -> Vec<usize> {
-        let row = self.data.get(&i);
-
-        match row {
-            None => vec![],
-            Some(row) => row.iter().map(|(i, _val)| *i).collect(),
-        }
+    /// ## neighbors_of
+    ///
+    /// Iterate over the column indices of all non zero entries in row `i`.
+    /// The row is looked up directly, so this takes `O(degree)`.
+    pub(crate) fn neighbors_of(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.data
+            .get(&i)
+            .into_iter()
+            .flat_map(|row| row.iter().map(|(j, _val)| *j))
     }
 }
 
@@ -114,6 +244,30 @@ where
     }
 }
 
+impl<T> Matrix<T>
+where
+    T: Zero + Clone + std::ops::Mul<Output = T> + Send + Sync,
+{
+    /// ## par_mul
+    ///
+    /// Multiply the Matrix with the vector `rhs`. The rows are distributed over the
+    /// threads of `parallelism`, the result is the same as for the serial multiplication.
+    pub fn par_mul(&self, rhs: &[T], parallelism: &Parallelism) -> Vec<T> {
+        assert_eq!(
+            self.ncols,
+            rhs.len(),
+            "dimensions of vector and matrix do not match"
+        );
+
+        parallelism.map_rows(self.nrows, |i| match self.data.get(&i) {
+            None => T::zero(),
+            Some(row) => row.iter().fold(T::zero(), |acc, (y, val)| {
+                acc + val.clone() * rhs[*y].clone()
+            }),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(feature = "extensor_bitvec")]
@@ -121,7 +275,8 @@ mod tests {
     #[cfg(feature = "extensor_dense_hashmap")]
     use crate::extensor::dense_hashmap::ExTensor;
 
-    use crate::matrix::sparse_hash::Matrix;
+    use crate::matrix::parallel::Parallelism;
+    use crate::matrix::{naive, sparse_hash::Matrix, sparse_triples};
     use crate::utils;
     use num_traits::identities::Zero;
     use std::collections::HashMap;
@@ -288,18 +443,78 @@ mod tests {
     #[test]
     fn neighbors() {
         let m: Matrix<u8> = Matrix::new(4, 4, vec![0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 1, 1, 0, 0, 0, 0]);
-        let neighbors_0 = m.neighbors_of(0);
+        let neighbors_0: Vec<_> = m.neighbors_of(0).collect();
         let expect_0 = vec![1];
-        let neighbors_1 = m.neighbors_of(1);
+        let neighbors_1: Vec<_> = m.neighbors_of(1).collect();
         let expect_1 = vec![0, 2];
-        let neighbors_2 = m.neighbors_of(2);
+        let neighbors_2: Vec<_> = m.neighbors_of(2).collect();
         let expect_2 = vec![1, 2, 3];
-        let neighbors_3 = m.neighbors_of(3);
-        let expect_3 = vec![];
+        let neighbors_3: Vec<_> = m.neighbors_of(3).collect();
+        let expect_3: Vec<usize> = vec![];
 
         assert_eq!(neighbors_0, expect_0);
         assert_eq!(neighbors_1, expect_1);
         assert_eq!(neighbors_2, expect_2);
         assert_eq!(neighbors_3, expect_3);
     }
+
+    #[test]
+    fn from_triplets() {
+        let m = Matrix::from_triplets(2, 3, vec![(1, 2, 3), (0, 1, 2), (1, 0, 0), (0, 1, 4)]);
+        let expect = Matrix::new(2, 3, vec![0, 4, 0, 0, 0, 3]);
+        assert_eq!(m, expect, "last value of a duplicate entry is used");
+        assert_eq!(m.nnz(), 2, "number of non zero entries");
+        assert_eq!(m.to_dense(), vec![0, 4, 0, 0, 0, 3], "dense values");
+    }
+
+    #[test]
+    fn set() {
+        let mut m = Matrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 3)]);
+        m.set(1, 0, 5);
+        m.set(0, 1, 4);
+        m.set(1, 2, 0);
+        assert_eq!(m.to_dense(), vec![0, 4, 0, 5, 0, 0], "entries are set");
+        assert_eq!(m.nnz(), 2, "zero entries are removed");
+    }
+
+    #[test]
+    fn grow() {
+        let mut m = Matrix::from_triplets(2, 2, vec![(0, 1, 2), (1, 0, 3)]);
+        m.grow(3, 3);
+        m.set(2, 2, 4);
+        assert_eq!(
+            m.to_dense(),
+            vec![0, 2, 0, 3, 0, 0, 0, 0, 4],
+            "entries are kept and new entries can be set"
+        );
+        assert_eq!(m.nnz(), 3, "no entries are added");
+    }
+
+    #[test]
+    fn convert() {
+        let m = Matrix::new(3, 2, vec![1, 0, 0, 2, 3, 0]);
+        let dense: naive::Matrix<i32> = m.clone().into();
+        let triples: sparse_triples::Matrix<i32> = m.clone().into();
+        assert_eq!(dense.data, vec![1, 0, 0, 2, 3, 0], "convert to naive");
+        assert_eq!(Matrix::from(dense), m, "round trip via naive");
+        assert_eq!(Matrix::from(triples), m, "round trip via sparse_triples");
+    }
+
+    #[test]
+    fn par_mul() {
+        let m = Matrix::new(3, 3, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let v = vec![1, 2, 3];
+        for num_threads in 1..=4 {
+            let parallelism = Parallelism::new(num_threads).with_min_rows_per_thread(1);
+            let r = m.par_mul(&v, &parallelism);
+            assert_eq!(r, vec![14, 32, 50], "parallel Matrix Vector multiplication");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions of vector and matrix do not match")]
+    fn par_mul_wrong_len() {
+        let m = Matrix::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        m.par_mul(&[1, 2], &Parallelism::serial());
+    }
 }
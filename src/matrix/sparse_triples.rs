@@ -3,6 +3,8 @@ use crate::extensor::bitvec::ExTensor;
 #[cfg(feature = "extensor_dense_hashmap")]
 use crate::extensor::dense_hashmap::ExTensor;
 
+use crate::matrix::parallel::Parallelism;
+use crate::matrix::{naive, sparse_hash, symmetric};
 use num_traits::identities::{One, Zero};
 use std::borrow::BorrowMut;
 
@@ -19,6 +21,7 @@ pub struct Matrix<T> {
 /// be clonable and must have a zero and a one element.
 /// Implements a sparse Matrix based on triples.
 /// The `(i, j)`-th Element with value `v` in the Matrix corresponds to the triple `(i, j, v)`.
+/// The triples are kept sorted by row, so a single row can be found via binary search.
 ///
 /// Example:
 ///
@@ -63,6 +66,120 @@ where
 
         Matrix { nrows, ncols, data }
     }
+
+    /// ## from_triplets
+    ///
+    /// Create a new Matrix from an iterator over `(row, col, value)` triplets, without
+    /// allocating a `nrows*ncols` buffer. Zero values are skipped. If an entry is given
+    /// multiple times, the last value is used.
+    pub fn from_triplets<I>(nrows: usize, ncols: usize, triplets: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, T)>,
+    {
+        let mut data: Vec<_> = triplets.into_iter().collect();
+        for (i, j, _val) in data.iter() {
+            assert!(*i < nrows && *j < ncols, "index out of bounds");
+        }
+
+        // stable sort keeps the last given value of a duplicate at the end
+        data.sort_by_key(|(i, j, _val)| (*i, *j));
+        data.reverse();
+        data.dedup_by_key(|(i, j, _val)| (*i, *j));
+        data.reverse();
+        data.retain(|(_i, _j, val)| !val.is_zero());
+
+        Matrix { nrows, ncols, data }
+    }
+
+    /// ## nnz
+    ///
+    /// Returns the number of stored non zero entries
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    /// ## to_dense
+    ///
+    /// Returns all `nrows*ncols` values in row major order
+    pub fn to_dense(&self) -> Vec<T> {
+        let mut res = vec![T::zero(); self.nrows * self.ncols];
+
+        for (i, j, val) in self.data.iter() {
+            res[i * self.ncols + j] = val.clone();
+        }
+
+        res
+    }
+
+    /// ## set
+    ///
+    /// Set the entry `(i, j)` to `val`. Setting an entry to zero removes it, so the triples stay
+    /// sorted and only contain non zero values.
+    pub fn set(&mut self, i: usize, j: usize, val: T) {
+        assert!(i < self.nrows && j < self.ncols, "index out of bounds");
+
+        match self
+            .data
+            .binary_search_by_key(&(i, j), |(x, y, _v)| (*x, *y))
+        {
+            Ok(pos) if val.is_zero() => {
+                self.data.remove(pos);
+            }
+            Ok(pos) => self.data[pos].2 = val,
+            Err(_pos) if val.is_zero() => {}
+            Err(pos) => self.data.insert(pos, (i, j, val)),
+        }
+    }
+
+    /// ## grow
+    ///
+    /// Add empty rows and columns, so the Matrix has `nrows` rows and `ncols` columns.
+    /// The stored entries are not changed.
+    pub fn grow(&mut self, nrows: usize, ncols: usize) {
+        assert!(
+            nrows >= self.nrows && ncols >= self.ncols,
+            "a Matrix can not shrink"
+        );
+        self.nrows = nrows;
+        self.ncols = ncols;
+    }
+
+    /// ## into_triplets
+    ///
+    /// Consume the Matrix and iterate over its non zero entries as `(row, col, value)` triplets
+    pub(crate) fn into_triplets(self) -> impl Iterator<Item = (usize, usize, T)> {
+        self.data.into_iter()
+    }
+}
+
+impl<T> From<naive::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: naive::Matrix<T>) -> Self {
+        let (nrows, ncols) = (m.nrows, m.ncols);
+        Matrix::from_triplets(nrows, ncols, m.into_triplets())
+    }
+}
+
+impl<T> From<sparse_hash::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: sparse_hash::Matrix<T>) -> Self {
+        let (nrows, ncols) = (m.nrows, m.ncols);
+        Matrix::from_triplets(nrows, ncols, m.into_triplets())
+    }
+}
+
+impl<T> From<symmetric::Matrix<T>> for Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    fn from(m: symmetric::Matrix<T>) -> Self {
+        let (nrows, ncols) = (m.nrows, m.ncols);
+        Matrix::from_triplets(nrows, ncols, m.into_triplets())
+    }
 }
 
 impl Matrix<u8> {
@@ -83,12 +200,15 @@ impl Matrix<u8> {
         }
     }
 
-    pub(crate) fn neighbors_of(&self, i: usize) -> Vec<usize> {
-        self.data
-            .iter()
-            .filter(|(row, _col, _v)| *row == i)
-            .map(|(_row, col, _val)| *col)
-            .collect()
+    /// ## neighbors_of
+    ///
+    /// Iterate over the column indices of all non zero entries in row `i`.
+    /// Since the triples are sorted by row this takes `O(log(nnz) + degree)`.
+    pub(crate) fn neighbors_of(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.data.partition_point(|(row, _col, _v)| *row < i);
+        let end = self.data.partition_point(|(row, _col, _v)| *row <= i);
+
+        self.data[start..end].iter().map(|(_row, col, _val)| *col)
     }
 }
 
@@ -109,6 +229,34 @@ where
     }
 }
 
+impl<T> Matrix<T>
+where
+    T: Zero + Clone + std::ops::Mul<Output = T> + Send + Sync,
+{
+    /// ## par_mul
+    ///
+    /// Multiply the Matrix with the vector `rhs`. The rows are distributed over the
+    /// threads of `parallelism`, the result is the same as for the serial multiplication.
+    pub fn par_mul(&self, rhs: &[T], parallelism: &Parallelism) -> Vec<T> {
+        assert_eq!(
+            self.ncols,
+            rhs.len(),
+            "dimensions of vector and matrix do not match"
+        );
+
+        parallelism.map_rows(self.nrows, |i| {
+            let start = self.data.partition_point(|(row, _col, _v)| *row < i);
+            let end = self.data.partition_point(|(row, _col, _v)| *row <= i);
+
+            self.data[start..end]
+                .iter()
+                .fold(T::zero(), |acc, (_x, y, v)| {
+                    acc + v.clone() * rhs[*y].clone()
+                })
+        })
+    }
+}
+
 impl<T> std::ops::Index<(usize, usize)> for Matrix<T> {
     type Output = T;
 
@@ -149,7 +297,8 @@ mod tests {
     #[cfg(feature = "extensor_dense_hashmap")]
     use crate::extensor::dense_hashmap::ExTensor;
 
-    use crate::matrix::sparse_triples::Matrix;
+    use crate::matrix::parallel::Parallelism;
+    use crate::matrix::{naive, sparse_hash, sparse_triples::Matrix, symmetric};
     use crate::utils;
     use num_traits::identities::Zero;
 
@@ -357,18 +506,87 @@ mod tests {
     #[test]
     fn neighbors() {
         let m: Matrix<u8> = Matrix::new(4, 4, vec![0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 1, 1, 0, 0, 0, 0]);
-        let neighbors_0 = m.neighbors_of(0);
+        let neighbors_0: Vec<_> = m.neighbors_of(0).collect();
         let expect_0 = vec![1];
-        let neighbors_1 = m.neighbors_of(1);
+        let neighbors_1: Vec<_> = m.neighbors_of(1).collect();
         let expect_1 = vec![0, 2];
-        let neighbors_2 = m.neighbors_of(2);
+        let neighbors_2: Vec<_> = m.neighbors_of(2).collect();
         let expect_2 = vec![1, 2, 3];
-        let neighbors_3 = m.neighbors_of(3);
-        let expect_3 = vec![];
+        let neighbors_3: Vec<_> = m.neighbors_of(3).collect();
+        let expect_3: Vec<usize> = vec![];
 
         assert_eq!(neighbors_0, expect_0);
         assert_eq!(neighbors_1, expect_1);
         assert_eq!(neighbors_2, expect_2);
         assert_eq!(neighbors_3, expect_3);
     }
+
+    #[test]
+    fn from_triplets() {
+        let m = Matrix::from_triplets(2, 3, vec![(1, 2, 3), (0, 1, 2), (1, 0, 0), (0, 1, 4)]);
+        let expect = vec![(0, 1, 4), (1, 2, 3)];
+        assert_eq!(m.data, expect, "triplets are sorted and deduplicated");
+        assert_eq!(m.nnz(), 2, "number of non zero entries");
+        assert_eq!(m.to_dense(), vec![0, 4, 0, 0, 0, 3], "dense values");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn from_triplets_out_of_bounds() {
+        Matrix::from_triplets(2, 2, vec![(0, 2, 1)]);
+    }
+
+    #[test]
+    fn set() {
+        let mut m = Matrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 3)]);
+        m.set(1, 0, 5);
+        m.set(0, 1, 4);
+        m.set(1, 2, 0);
+        assert_eq!(m.to_dense(), vec![0, 4, 0, 5, 0, 0], "entries are set");
+        assert_eq!(m.nnz(), 2, "zero entries are removed");
+    }
+
+    #[test]
+    fn grow() {
+        let mut m = Matrix::from_triplets(2, 2, vec![(0, 1, 2), (1, 0, 3)]);
+        m.grow(3, 3);
+        m.set(2, 2, 4);
+        assert_eq!(
+            m.to_dense(),
+            vec![0, 2, 0, 3, 0, 0, 0, 0, 4],
+            "entries are kept and new entries can be set"
+        );
+        assert_eq!(m.nnz(), 3, "no entries are added");
+    }
+
+    #[test]
+    fn convert() {
+        let m = Matrix::new(3, 2, vec![1, 0, 0, 2, 3, 0]);
+        let dense: naive::Matrix<i32> = m.clone().into();
+        let hash: sparse_hash::Matrix<i32> = m.clone().into();
+        assert_eq!(Matrix::from(dense), m, "round trip via naive");
+        assert_eq!(Matrix::from(hash), m, "round trip via sparse_hash");
+
+        let sym = symmetric::Matrix::new(2, 2, vec![1, 2, 2, 0]);
+        let full: Matrix<i32> = sym.into();
+        assert_eq!(full.to_dense(), vec![1, 2, 2, 0], "symmetric is mirrored");
+    }
+
+    #[test]
+    fn par_mul() {
+        let m = Matrix::new(3, 3, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let v = vec![1, 2, 3];
+        for num_threads in 1..=4 {
+            let parallelism = Parallelism::new(num_threads).with_min_rows_per_thread(1);
+            let r = m.par_mul(&v, &parallelism);
+            assert_eq!(r, vec![14, 32, 50], "parallel Matrix Vector multiplication");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions of vector and matrix do not match")]
+    fn par_mul_wrong_len() {
+        let m = Matrix::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        m.par_mul(&[1, 2], &Parallelism::serial());
+    }
 }
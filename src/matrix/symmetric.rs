@@ -0,0 +1,433 @@
+#[cfg(feature = "extensor_bitvec")]
+use crate::extensor::bitvec::ExTensor;
+#[cfg(feature = "extensor_dense_hashmap")]
+use crate::extensor::dense_hashmap::ExTensor;
+
+use crate::matrix::parallel::Parallelism;
+use num_traits::identities::{One, Zero};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<T> {
+    pub nrows: usize,
+    pub ncols: usize,
+    data: Vec<(usize, usize, T)>,
+    /// transposed index of the strict upper triangle: `(j, i)` for every stored `(i, j)` with
+    /// `i < j`, sorted, so the mirrored entries of a row are one contiguous slice
+    lower: Vec<(usize, usize)>,
+}
+
+/// # Matrix
+///
+/// Create a symmetric Matrix that stores values of type `T`. `T` only needs to
+/// be clonable and must have a zero and a one element.
+/// Only the upper triangle (including the diagonal) is stored as triples `(i, j, v)` with `i <= j`,
+/// sorted by row. The entry `(j, i)` is implicitly equal to the entry `(i, j)`.
+/// The positions of these mirrored entries are indexed by column, so `neighbors_of` reads both
+/// halves of a row as contiguous slices. The stored triples are therefore private, they are
+/// changed through `set` and read through `entries`.
+///
+/// Example:
+///
+/// ```no code
+/// | 0 1 1 |
+/// | 1 0 0 |     ------>     vec![ (0, 1, 1), (0, 2, 1) ]
+/// | 1 0 0 |
+/// ```
+impl<T> Matrix<T>
+where
+    T: Clone + One + Zero,
+{
+    /// ## new
+    ///
+    /// Create a new symmetric Matrix.
+    ///
+    /// Arguments:
+    ///
+    /// `nrows`: number of rows
+    /// `ncols`: number of columns, must be equal to `nrows`
+    /// `values`: Vec of values, size of Vec must be nrows*ncols. Only the upper triangle is read.
+    ///
+    pub fn new(nrows: usize, ncols: usize, values: Vec<T>) -> Self {
+        assert_eq!(nrows, ncols, "symmetric matrix must be square");
+        assert_eq!(
+            values.len(),
+            nrows * ncols,
+            "dimensons of values does not match"
+        );
+
+        let mut data = Vec::new();
+
+        for (i, val) in values.into_iter().enumerate() {
+            let row_index = i / ncols;
+            let col_index = i % ncols;
+
+            if row_index <= col_index && !val.is_zero() {
+                data.push((row_index, col_index, val));
+            }
+        }
+
+        let lower = Matrix::index_lower(&data);
+        Matrix {
+            nrows,
+            ncols,
+            data,
+            lower,
+        }
+    }
+
+    /// ## from_triplets
+    ///
+    /// Create a new symmetric Matrix from an iterator over `(row, col, value)` triplets, without
+    /// allocating a `nrows*ncols` buffer. A triplet `(j, i, v)` of the lower triangle is
+    /// stored as `(i, j, v)`. Zero values are skipped. If an entry is given multiple times,
+    /// the last value is used.
+    pub fn from_triplets<I>(nrows: usize, ncols: usize, triplets: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, T)>,
+    {
+        assert_eq!(nrows, ncols, "symmetric matrix must be square");
+
+        let mut data: Vec<_> = triplets
+            .into_iter()
+            .map(|(i, j, val)| {
+                assert!(i < nrows && j < ncols, "index out of bounds");
+                if i <= j {
+                    (i, j, val)
+                } else {
+                    (j, i, val)
+                }
+            })
+            .collect();
+
+        // stable sort keeps the last given value of a duplicate at the end
+        data.sort_by_key(|(i, j, _val)| (*i, *j));
+        data.reverse();
+        data.dedup_by_key(|(i, j, _val)| (*i, *j));
+        data.reverse();
+        data.retain(|(_i, _j, val)| !val.is_zero());
+
+        let lower = Matrix::index_lower(&data);
+        Matrix {
+            nrows,
+            ncols,
+            data,
+            lower,
+        }
+    }
+
+    /// ## index_lower
+    ///
+    /// the sorted positions `(j, i)` of the mirrored entries of the strict upper triangle
+    fn index_lower(data: &[(usize, usize, T)]) -> Vec<(usize, usize)> {
+        let mut lower: Vec<_> = data
+            .iter()
+            .filter(|(i, j, _val)| i < j)
+            .map(|(i, j, _val)| (*j, *i))
+            .collect();
+        lower.sort_unstable();
+        lower
+    }
+
+    /// ## nnz
+    ///
+    /// Returns the number of non zero entries of the full matrix,
+    /// so every stored entry that is not on the diagonal counts twice
+    pub fn nnz(&self) -> usize {
+        self.data
+            .iter()
+            .map(|(i, j, _val)| if i == j { 1 } else { 2 })
+            .sum()
+    }
+
+    /// ## entries
+    ///
+    /// Returns the stored triples `(i, j, v)` of the upper triangle, sorted by row
+    pub fn entries(&self) -> &[(usize, usize, T)] {
+        &self.data
+    }
+
+    /// ## to_dense
+    /// ## to_dense
+    ///
+    /// Returns all `nrows*ncols` values of the full matrix in row major order
+    pub fn to_dense(&self) -> Vec<T> {
+        let mut res = vec![T::zero(); self.nrows * self.ncols];
+
+        for (i, j, val) in self.data.iter() {
+            res[i * self.ncols + j] = val.clone();
+            res[j * self.ncols + i] = val.clone();
+        }
+
+        res
+    }
+
+    /// ## set
+    ///
+    /// Set the entries `(i, j)` and `(j, i)` to `val`. Setting an entry to zero removes it.
+    pub fn set(&mut self, i: usize, j: usize, val: T) {
+        assert!(i < self.nrows && j < self.ncols, "index out of bounds");
+        let (i, j) = (i.min(j), i.max(j));
+
+        match self
+            .data
+            .binary_search_by_key(&(i, j), |(x, y, _v)| (*x, *y))
+        {
+            Ok(pos) if val.is_zero() => {
+                self.data.remove(pos);
+                if i != j {
+                    let pos = self
+                        .lower
+                        .binary_search(&(j, i))
+                        .expect("mirrored entry is indexed");
+                    self.lower.remove(pos);
+                }
+            }
+            Ok(pos) => self.data[pos].2 = val,
+            Err(_pos) if val.is_zero() => {}
+            Err(pos) => {
+                self.data.insert(pos, (i, j, val));
+                if i != j {
+                    let pos = self.lower.binary_search(&(j, i)).unwrap_or_else(|pos| pos);
+                    self.lower.insert(pos, (j, i));
+                }
+            }
+        }
+    }
+
+    /// ## grow
+    ///
+    /// Add empty rows and columns, so the Matrix has `n` rows and `n` columns.
+    /// The stored entries are not changed.
+    pub fn grow(&mut self, n: usize) {
+        assert!(n >= self.nrows, "a Matrix can not shrink");
+        self.nrows = n;
+        self.ncols = n;
+    }
+
+    /// ## into_triplets
+    ///
+    /// Consume the Matrix and iterate over all non zero entries of the full matrix as
+    /// `(row, col, value)` triplets
+    pub(crate) fn into_triplets(self) -> impl Iterator<Item = (usize, usize, T)> {
+        self.data.into_iter().flat_map(|(i, j, val)| {
+            let mirrored = if i != j {
+                Some((j, i, val.clone()))
+            } else {
+                None
+            };
+            std::iter::once((i, j, val)).chain(mirrored)
+        })
+    }
+}
+
+impl Matrix<u8> {
+    /// ## add_coding
+    ///
+    /// Multiply every row `i` with `coding[i]`. The result is not symmetric anymore,
+    /// so instead of materializing it, the returned matrix only keeps references to `self` and
+    /// the coding.
+    pub(crate) fn add_coding<'a>(&'a self, coding: &'a [ExTensor]) -> CodedMatrix<'a> {
+        CodedMatrix {
+            pattern: self,
+            coding,
+        }
+    }
+
+    /// ## neighbors_of
+    ///
+    /// Iterate over the column indices of all non zero entries in row `i`.
+    /// Entries of the upper triangle and the mirrored entries of the lower triangle are both
+    /// found via binary search, so this takes `O(log(nnz) + degree)`.
+    pub(crate) fn neighbors_of(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.data.partition_point(|(row, _col, _v)| *row < i);
+        let end = self.data.partition_point(|(row, _col, _v)| *row <= i);
+        let lower_start = self.lower.partition_point(|(row, _col)| *row < i);
+        let lower_end = self.lower.partition_point(|(row, _col)| *row <= i);
+
+        let lower = self.lower[lower_start..lower_end]
+            .iter()
+            .map(|(_row, col)| *col);
+        let upper = self.data[start..end].iter().map(|(_row, col, _v)| *col);
+
+        lower.chain(upper)
+    }
+}
+
+impl<T> std::ops::Mul<Vec<T>> for &Matrix<T>
+where
+    T: Zero + Clone + std::ops::Mul<Output = T>,
+{
+    type Output = Vec<T>;
+
+    fn mul(self, rhs: Vec<T>) -> Vec<T> {
+        let mut res = vec![T::zero(); self.nrows];
+
+        for (x, y, v) in self.data.iter() {
+            res[*x] = res[*x].clone() + v.clone() * rhs[*y].clone();
+            if x != y {
+                res[*y] = res[*y].clone() + v.clone() * rhs[*x].clone();
+            }
+        }
+
+        res
+    }
+}
+
+/// # CodedMatrix
+///
+/// A symmetric 0/1 matrix where every row `i` is multiplied by `coding[i]`.
+/// The `(i, j)`-th entry is `coding[i]` if `(i, j)` is non zero in `pattern` and zero otherwise.
+#[derive(Debug, Clone)]
+pub struct CodedMatrix<'a> {
+    pattern: &'a Matrix<u8>,
+    coding: &'a [ExTensor],
+}
+
+impl std::ops::Mul<Vec<ExTensor>> for &CodedMatrix<'_> {
+    type Output = Vec<ExTensor>;
+
+    fn mul(self, rhs: Vec<ExTensor>) -> Vec<ExTensor> {
+        self.par_mul(&rhs, &Parallelism::serial())
+    }
+}
+
+impl CodedMatrix<'_> {
+    /// ## par_mul
+    ///
+    /// Multiply the Matrix with the vector `rhs`.
+    /// Since every value in row `i` is `coding[i]`, the neighbors of each row are summed up
+    /// first and then multiplied once with `coding[i]`. The sums are computed serially,
+    /// only the multiplications are distributed over the threads of `parallelism`.
+    pub fn par_mul(&self, rhs: &[ExTensor], parallelism: &Parallelism) -> Vec<ExTensor> {
+        let mut sums = vec![ExTensor::zero(); self.pattern.nrows];
+
+        for (x, y, _v) in self.pattern.data.iter() {
+            sums[*x] = &sums[*x] + &rhs[*y];
+            if x != y {
+                sums[*y] = &sums[*y] + &rhs[*x];
+            }
+        }
+
+        parallelism.map_rows(self.pattern.nrows, |i| &self.coding[i] * &sums[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::{sparse_triples, symmetric::Matrix};
+    use crate::utils;
+
+    #[test]
+    fn create() {
+        let m = Matrix::new(3, 3, vec![0, 1, 1, 1, 0, 0, 1, 0, 1]);
+        let expect = vec![(0, 1, 1), (0, 2, 1), (2, 2, 1)];
+        assert_eq!(
+            m.entries(),
+            expect,
+            "only the upper triangle should be stored"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "symmetric matrix must be square")]
+    fn create_rect() {
+        Matrix::new(2, 3, vec![0, 1, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn mat_vec_mul() {
+        let m = Matrix::new(3, 3, vec![1, 2, 3, 2, 4, 5, 3, 5, 6]);
+        let v = vec![1, 2, 3];
+        let r = &m * v;
+        assert_eq!(
+            r,
+            vec![14, 25, 31],
+            "symmetric Matrix Vector multiplication"
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let m: Matrix<u8> = Matrix::new(4, 4, vec![0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 1, 1, 0, 0, 1, 0]);
+        let neighbors_0: Vec<_> = m.neighbors_of(0).collect();
+        let neighbors_1: Vec<_> = m.neighbors_of(1).collect();
+        let neighbors_2: Vec<_> = m.neighbors_of(2).collect();
+        let neighbors_3: Vec<_> = m.neighbors_of(3).collect();
+
+        assert_eq!(neighbors_0, vec![1]);
+        assert_eq!(neighbors_1, vec![0, 2]);
+        assert_eq!(neighbors_2, vec![1, 2, 3]);
+        assert_eq!(neighbors_3, vec![2]);
+    }
+
+    #[test]
+    fn coding() {
+        let k = 3;
+        let n = 4;
+        let values = vec![0, 1, 0, 1, 1, 0, 1, 1, 0, 1, 0, 1, 1, 1, 1, 0];
+        let coding = utils::create_vandermonde(n, k);
+
+        let full: sparse_triples::Matrix<u8> = sparse_triples::Matrix::new(n, n, values.clone());
+        let sym: Matrix<u8> = Matrix::new(n, n, values);
+
+        let full_res = &full.add_coding(&coding) * coding.clone();
+        let sym_res = &sym.add_coding(&coding) * coding.clone();
+
+        for (f, s) in full_res.into_iter().zip(sym_res) {
+            assert_eq!(
+                f.terms(),
+                s.terms(),
+                "coded symmetric matrix should behave like the full matrix"
+            );
+        }
+    }
+
+    #[test]
+    fn from_triplets() {
+        let m = Matrix::from_triplets(3, 3, vec![(1, 0, 1), (0, 2, 1), (2, 2, 1), (0, 1, 1)]);
+        let expect = vec![(0, 1, 1), (0, 2, 1), (2, 2, 1)];
+        assert_eq!(m.entries(), expect, "lower triangle is mirrored");
+        assert_eq!(m.nnz(), 5, "number of non zero entries of the full matrix");
+        assert_eq!(
+            m.to_dense(),
+            vec![0, 1, 1, 1, 0, 0, 1, 0, 1],
+            "dense values of the full matrix"
+        );
+    }
+
+    #[test]
+    fn set() {
+        let mut m = Matrix::from_triplets(3, 3, vec![(0, 1, 1), (2, 2, 1)]);
+        m.set(2, 0, 1);
+        m.set(1, 0, 0);
+        assert_eq!(
+            m.entries(),
+            vec![(0, 2, 1), (2, 2, 1)],
+            "only the upper triangle is changed"
+        );
+        assert_eq!(
+            m.to_dense(),
+            vec![0, 0, 1, 0, 0, 0, 1, 0, 1],
+            "dense values of the full matrix"
+        );
+        assert_eq!(m.neighbors_of(1).count(), 0, "mirrored entry is removed");
+        assert_eq!(
+            m.neighbors_of(2).collect::<Vec<_>>(),
+            vec![0, 2],
+            "mirrored entry is added"
+        );
+    }
+
+    #[test]
+    fn grow() {
+        let mut m = Matrix::from_triplets(2, 2, vec![(0, 1, 1)]);
+        m.grow(3);
+        m.set(2, 1, 1);
+        assert_eq!(
+            m.to_dense(),
+            vec![0, 1, 0, 1, 0, 1, 0, 1, 0],
+            "entries are kept and new entries can be set"
+        );
+        assert_eq!(m.neighbors_of(1).collect::<Vec<_>>(), vec![0, 2]);
+    }
+}
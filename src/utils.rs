@@ -3,6 +3,7 @@ use crate::extensor::bitvec::ExTensor;
 #[cfg(feature = "extensor_dense_hashmap")]
 use crate::extensor::dense_hashmap::ExTensor;
 
+use crate::graph::GraphError;
 use rand::distributions::{Distribution, Uniform};
 
 /// ## create_vandermonde
@@ -57,48 +58,141 @@ pub(crate) fn create_bernoulli(n: usize, k: usize) -> Vec<ExTensor> {
     res
 }
 
+/// # Format
+///
+/// the nauty formats that `file_n_from` can detect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Graph6,
+    Sparse6,
+    Digraph6,
+}
+
+/// ## strip_header
+///
+/// remove a `>>graph6<<`, `>>sparse6<<` or `>>digraph6<<` header from the start of `data`
+pub(crate) fn strip_header(data: &[u8]) -> &[u8] {
+    [&b">>graph6<<"[..], b">>sparse6<<", b">>digraph6<<"]
+        .iter()
+        .find_map(|header| data.strip_prefix(*header))
+        .unwrap_or(data)
+}
+
 /// ## file_n_from
 ///
 /// given a `path_str` which is the path to a graph6 file as a string, it opens the file and returns
-/// the file with `n` which is the number of vertices in that graph.
-/// It works with .g6 and .s6 files, with and without headers. If the file is not found it will panic.
-pub(crate) fn file_n_from(path_str: &str) -> (Vec<u8>, usize) {
-    // read file if it exists
-    let mut file = std::fs::read(path_str).expect(".graph6 input file not found");
-
-    let mut _n = 0;
-
-    let has_sparse_header =
-        file.len() > 10 && std::str::from_utf8(&file[..11]).unwrap() == ">>sparse6<<";
-    let has_graph_header =
-        file.len() > 9 && std::str::from_utf8(&file[..10]).unwrap() == ">>graph6<<";
-    let is_sparse = file[0] as char == ':' || has_sparse_header;
-
-    if !is_sparse {
-        if has_graph_header {
-            _n = (file[10] - 63) as usize;
-            file = file[11..].to_vec();
-        } else {
-            _n = (file[0] - 63) as usize;
-            file = file[1..].to_vec();
-        }
-    } else if has_sparse_header {
-        _n = (file[12] - 63) as usize;
-        file = file[13..].to_vec();
-    } else {
-        _n = (file[1] - 63) as usize;
-        file = file[2..].to_vec();
+/// the data of the first graph with `n` which is the number of vertices in that graph and the
+/// detected Format, see `line_n_from`.
+/// It works with .g6, .s6 and .d6 files, with and without headers.
+pub(crate) fn file_n_from(path_str: &str) -> Result<(Vec<u8>, usize, Format), GraphError> {
+    let file = std::fs::read(path_str)?;
+
+    let data = strip_header(&file);
+    let offset = file.len() - data.len();
+    let line = data.split(|b| *b == b'\n').next().unwrap_or(&[]);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+    let (format, n, data) = line_n_from(line, 1, offset)?;
+
+    Ok((data.to_vec(), n, format))
+}
+
+/// ## line_n_from
+///
+/// detect the Format of a single graph6, sparse6 or digraph6 `line` (without header and newline)
+/// and decode its number of vertices `n`. Returns the Format, `n` and the remaining data.
+/// All characters are checked and for graph6 and digraph6 the data must be long enough for
+/// `n` vertices. A `BadHeader` is returned if the adjacency matrix of `n` vertices has more
+/// bits than fit into a `usize`. `line_no` and `offset` (the position of `line` within the line
+/// of the file) are used for the position of an error.
+pub(crate) fn line_n_from(
+    line: &[u8],
+    line_no: usize,
+    offset: usize,
+) -> Result<(Format, usize, &[u8]), GraphError> {
+    let (format, start) = match line.first() {
+        Some(b':') | Some(b';') => (Format::Sparse6, 1),
+        Some(b'&') => (Format::Digraph6, 1),
+        _ => (Format::Graph6, 0),
+    };
+
+    if let Some(i) = line[start..].iter().position(|b| !(63..=126).contains(b)) {
+        return Err(GraphError::BadChar {
+            line: line_no,
+            byte: offset + start + i,
+            found: line[start + i] as char,
+        });
     }
+    let truncated = GraphError::Truncated {
+        line: line_no,
+        byte: offset + line.len(),
+    };
+    let (n, data) = n_from(&line[start..]).ok_or(truncated)?;
 
-    if _n > 62 {
-        let n1 = ((file[0] - 63) as i32) << 12;
-        let n2 = ((file[1] - 63) as i32) << 6;
-        let n3 = (file[2] - 63) as i32;
-        _n = (n1 + n2 + n3) as usize;
-        file = file[3..].to_vec();
+    // n can be up to 2^36 - 1, so the number of bits may not fit into usize
+    let num_bits = match format {
+        Format::Graph6 => n.checked_mul(n.saturating_sub(1)).map(|b| b / 2),
+        Format::Digraph6 => n.checked_mul(n),
+        Format::Sparse6 => Some(0),
+    }
+    .ok_or_else(|| GraphError::BadHeader {
+        line: line_no,
+        message: String::from("number of vertices is too large"),
+    })?;
+    if data.len() < num_bits.div_ceil(6) {
+        return Err(GraphError::Truncated {
+            line: line_no,
+            byte: offset + line.len(),
+        });
     }
 
-    (file, _n)
+    Ok((format, n, data))
+}
+
+/// ## n_from
+///
+/// decode the number of vertices `n` at the start of graph6 / sparse6 `data` and return it
+/// together with the remaining data, or `None` if `data` is too short.
+/// If `n <= 62`, then `n` is stored in one byte as `n + 63`. If `n <= 258047`, the first byte
+/// is `126` and `n` is stored in the next three bytes (6 bits each). Otherwise the first two
+/// bytes are `126` and `n` is stored in the next six bytes.
+pub(crate) fn n_from(data: &[u8]) -> Option<(usize, &[u8])> {
+    let (start, num_bytes) = match data {
+        [] => return None,
+        [126, 126, ..] => (2, 6),
+        [126, ..] => (1, 3),
+        _ => return Some(((data[0] - 63) as usize, &data[1..])),
+    };
+    if data.len() < start + num_bytes {
+        return None;
+    }
+
+    let n = data[start..start + num_bytes]
+        .iter()
+        .fold(0, |n, b| n << 6 | (b - 63) as usize);
+    Some((n, &data[start + num_bytes..]))
+}
+
+/// ## n_to
+///
+/// encode the number of vertices `n` for graph6 / sparse6 data, the inverse of `n_from`
+pub(crate) fn n_to(n: usize) -> Vec<u8> {
+    if n <= 62 {
+        return vec![n as u8 + 63];
+    }
+
+    let (mut res, num_bytes) = if n <= 258047 {
+        (vec![126], 3)
+    } else {
+        assert!(n < 1 << 36, "graph is too large for graph6");
+        (vec![126, 126], 6)
+    };
+    res.extend(
+        (0..num_bytes)
+            .rev()
+            .map(|i| ((n >> (6 * i)) & 63) as u8 + 63),
+    );
+    res
 }
 
 /// ## factorial
@@ -179,7 +273,9 @@ mod tests {
     #[cfg(feature = "extensor_dense_hashmap")]
     use crate::extensor::dense_hashmap::ExTensor;
 
-    use crate::utils::{create_bernoulli, create_vandermonde, factorial, has_intersection};
+    use crate::utils::{
+        create_bernoulli, create_vandermonde, factorial, has_intersection, n_from, n_to,
+    };
 
     #[test]
     fn vandermonde() {
@@ -249,4 +345,29 @@ mod tests {
         let res_3 = has_intersection(&v_1, &v_4);
         assert_eq!(res_3, false);
     }
+
+    #[test]
+    fn encode_n() {
+        assert_eq!(n_to(10), b"I".to_vec(), "small n is stored in one byte");
+        assert_eq!(
+            n_to(100),
+            b"~?@c".to_vec(),
+            "large n is stored in four bytes"
+        );
+        // example from the nauty documentation
+        assert_eq!(
+            n_to(460175067),
+            b"~~?ZZZZZ".to_vec(),
+            "huge n is stored in eight bytes"
+        );
+        for n in [0, 1, 62, 63, 100, 1280, 258047, 258048, 460175067].iter() {
+            let bytes = n_to(*n);
+            assert_eq!(
+                n_from(&bytes),
+                Some((*n, &[][..])),
+                "decoding is the inverse"
+            );
+        }
+        assert_eq!(n_from(b"~?@"), None, "truncated size");
+    }
 }